@@ -1,3 +1,63 @@
+use std::collections::{BTreeSet, HashSet};
+use std::env;
+use std::fs;
+use std::path::Path;
+
+#[allow(dead_code)]
+#[path = "src/word_filter.rs"]
+mod word_filter;
+
+const DATA_DIR: &str = "src/data";
+const SOURCE_FILES: [&str; 4] = [
+    "wordlist.txt",
+    "core_words.txt",
+    "cspell-words.txt",
+    "invalid-words.txt",
+];
+
 fn main() {
+    compile_dictionary();
     tauri_build::build()
 }
+
+/// Intersects the bundled word lists once at compile time and writes the
+/// surviving words as an FST the solver embeds with `include_bytes!`. The
+/// source lists ship as bundle resources and are only read at runtime.
+fn compile_dictionary() {
+    for file in SOURCE_FILES {
+        println!("cargo:rerun-if-changed={DATA_DIR}/{file}");
+    }
+    println!("cargo:rerun-if-changed=src/word_filter.rs");
+
+    let out_dir = env::var("OUT_DIR").expect("OUT_DIR is set by cargo");
+    let out_dir = Path::new(&out_dir);
+
+    let wordlist = read_word_set("wordlist.txt");
+    let core_words = read_word_set("core_words.txt");
+    let cspell_words = read_word_set("cspell-words.txt");
+    let blocklist = read_word_set("invalid-words.txt");
+
+    let words: BTreeSet<&String> = wordlist
+        .iter()
+        .filter(|word| {
+            word_filter::first_rejection(word, &core_words, &cspell_words, &blocklist).is_none()
+        })
+        .collect();
+    write_fst(&out_dir.join("dictionary.fst"), words);
+}
+
+fn write_fst(path: &Path, words: BTreeSet<&String>) {
+    let set = fst::Set::from_iter(words).expect("word lists are sorted and unique");
+    fs::write(path, set.as_fst().as_bytes())
+        .unwrap_or_else(|err| panic!("failed to write {}: {err}", path.display()));
+}
+
+fn read_word_set(file: &str) -> HashSet<String> {
+    word_filter::parse_word_list(&read_data_file(file)).collect()
+}
+
+fn read_data_file(file: &str) -> String {
+    let path = Path::new(DATA_DIR).join(file);
+    fs::read_to_string(&path)
+        .unwrap_or_else(|err| panic!("failed to read {}: {err}", path.display()))
+}
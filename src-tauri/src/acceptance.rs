@@ -0,0 +1,150 @@
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::dictionary::{Dictionary, SourceKind};
+use crate::storage;
+use crate::word_filter::WordSet;
+
+/// How many confirmed plays the membership prior is worth.
+const PRIOR_WEIGHT: f64 = 4.0;
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct Outcomes {
+    pub accepted: u32,
+    pub rejected: u32,
+}
+
+/// Which word lists vouch for a word, used as the prior before any plays are logged.
+#[derive(Debug, Clone, Copy)]
+pub struct Membership {
+    pub core_words: bool,
+    pub cspell_words: bool,
+    pub allowlisted: bool,
+}
+
+impl Membership {
+    /// Every filtered dictionary word is on both lists, so the source lists
+    /// are only read for allowlisted words and words outside the dictionary.
+    /// If they can't be read, those words count as on neither list.
+    pub fn of(dictionary: &Dictionary, word: &str) -> Self {
+        let allowlisted = dictionary.is_allowlisted(word);
+        if dictionary.contains(word) && !allowlisted {
+            return Self {
+                core_words: true,
+                cspell_words: true,
+                allowlisted,
+            };
+        }
+        Self {
+            core_words: dictionary.source(SourceKind::CoreWords).contains_word(word),
+            cspell_words: dictionary
+                .source(SourceKind::CspellWords)
+                .contains_word(word),
+            allowlisted,
+        }
+    }
+
+    fn prior(self) -> f64 {
+        match (self.core_words, self.cspell_words) {
+            (true, true) => 0.9,
+            _ if self.allowlisted => 0.75,
+            (false, true) => 0.6,
+            (true, false) => 0.5,
+            (false, false) => 0.3,
+        }
+    }
+}
+
+/// Posterior mean of a beta prior seeded from list membership and updated
+/// with every accept/reject the user has confirmed in game.
+pub fn confidence(outcomes: Outcomes, membership: Membership) -> f64 {
+    let accepted = f64::from(outcomes.accepted);
+    let rejected = f64::from(outcomes.rejected);
+    (membership.prior() * PRIOR_WEIGHT + accepted) / (PRIOR_WEIGHT + accepted + rejected)
+}
+
+/// Per-word accept/reject counts, persisted as JSON in the app data dir.
+pub struct AcceptanceLog {
+    path: PathBuf,
+    words: BTreeMap<String, Outcomes>,
+}
+
+impl AcceptanceLog {
+    pub fn open(path: PathBuf) -> Result<Self, String> {
+        let words = match storage::read_optional(&path)? {
+            Some(text) => serde_json::from_str(&text)
+                .map_err(|err| format!("Failed to parse {}: {err}", path.display()))?,
+            None => BTreeMap::new(),
+        };
+        Ok(Self { path, words })
+    }
+
+    pub fn outcomes(&self, word: &str) -> Outcomes {
+        self.words.get(word).copied().unwrap_or_default()
+    }
+
+    pub fn entries(&self) -> impl Iterator<Item = (&str, Outcomes)> {
+        self.words
+            .iter()
+            .map(|(word, outcomes)| (word.as_str(), *outcomes))
+    }
+
+    /// Counts one more outcome for `word`. Memory only changes once the
+    /// log has been written, so a failed write leaves both as they were.
+    pub fn record(&mut self, word: &str, accepted: bool) -> Result<Outcomes, String> {
+        let mut outcomes = self.outcomes(word);
+        if accepted {
+            outcomes.accepted = outcomes.accepted.saturating_add(1);
+        } else {
+            outcomes.rejected = outcomes.rejected.saturating_add(1);
+        }
+        let mut words = self.words.clone();
+        words.insert(word.to_string(), outcomes);
+        let text = serde_json::to_string_pretty(&words)
+            .map_err(|err| format!("Failed to encode word outcomes: {err}"))?;
+        storage::write_atomic(&self.path, &text)?;
+        self.words = words;
+        Ok(outcomes)
+    }
+
+    pub fn confidence(&self, dictionary: &Dictionary, word: &str) -> f64 {
+        confidence(self.outcomes(word), Membership::of(dictionary, word))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn history_moves_confidence_away_from_the_prior() {
+        let listed = Membership {
+            core_words: true,
+            cspell_words: true,
+            allowlisted: false,
+        };
+        let cspell_only = Membership {
+            core_words: false,
+            cspell_words: true,
+            allowlisted: false,
+        };
+
+        let fresh = confidence(Outcomes::default(), listed);
+        assert!((fresh - 0.9).abs() < 1e-9);
+        assert!(confidence(Outcomes::default(), cspell_only) < fresh);
+
+        let rejected = Outcomes {
+            accepted: 0,
+            rejected: 3,
+        };
+        assert!(confidence(rejected, listed) < 0.6);
+
+        let accepted = Outcomes {
+            accepted: 6,
+            rejected: 0,
+        };
+        assert!(confidence(accepted, cspell_only) > 0.8);
+    }
+}
@@ -0,0 +1,221 @@
+use std::collections::HashMap;
+
+pub type Signature = [u8; 26];
+
+/// Dictionary words bucketed by letter multiset, so a rack only visits words it can spell.
+/// The words share one buffer and each bucket holds their offsets; a word's
+/// length is the letter count of its bucket's signature.
+pub struct AnagramIndex {
+    text: String,
+    buckets: HashMap<Signature, Vec<u32>>,
+    max_word_len: usize,
+}
+
+impl AnagramIndex {
+    pub fn new<I, S>(words: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut text = String::new();
+        let mut buckets: HashMap<Signature, Vec<u32>> = HashMap::new();
+        let mut max_word_len = 0;
+        for word in words {
+            let word = word.as_ref();
+            let Some(signature) = signature_of(word) else {
+                continue;
+            };
+            max_word_len = max_word_len.max(word.len());
+            let offset = u32::try_from(text.len()).expect("the word buffer fits in u32 offsets");
+            text.push_str(word);
+            buckets.entry(signature).or_default().push(offset);
+        }
+        for (signature, bucket) in &mut buckets {
+            let len = signature_len(signature);
+            bucket.sort_by(|a, b| word_at(&text, *a, len).cmp(word_at(&text, *b, len)));
+            bucket.dedup_by(|a, b| word_at(&text, *a, len) == word_at(&text, *b, len));
+        }
+        Self {
+            text,
+            buckets,
+            max_word_len,
+        }
+    }
+
+    pub fn word_count(&self) -> usize {
+        self.buckets.values().map(Vec::len).sum()
+    }
+
+    pub fn contains(&self, word: &str) -> bool {
+        signature_of(word)
+            .and_then(|signature| self.buckets.get(&signature))
+            .is_some_and(|bucket| {
+                bucket
+                    .binary_search_by(|&offset| word_at(&self.text, offset, word.len()).cmp(word))
+                    .is_ok()
+            })
+    }
+
+    #[cfg(test)]
+    pub fn words(&self) -> impl Iterator<Item = &str> {
+        let text = self.text.as_str();
+        self.buckets.iter().flat_map(move |(signature, bucket)| {
+            let len = signature_len(signature);
+            bucket.iter().map(move |&offset| word_at(text, offset, len))
+        })
+    }
+
+    /// Calls `visit` for every indexed word that can be formed from `rack_counts`
+    /// plus up to `blanks` wildcard tiles. When `length` is set only words of
+    /// exactly that length are visited. Each letter multiset is looked up once:
+    /// a blank only stands for a letter whose rack copies are all used.
+    pub fn for_each_fit<F>(
+        &self,
+        rack_counts: &Signature,
+        blanks: usize,
+        length: Option<usize>,
+        mut visit: F,
+    ) where
+        F: FnMut(&str),
+    {
+        let letters: Vec<usize> = (0..26).filter(|&idx| rack_counts[idx] > 0).collect();
+        let rack_letters: usize = rack_counts.iter().map(|&count| usize::from(count)).sum();
+        let rack_len = rack_letters + blanks;
+        let max_len = length
+            .unwrap_or(rack_len)
+            .min(rack_len)
+            .min(self.max_word_len);
+        let min_len = length.unwrap_or(1);
+        if min_len > max_len {
+            return;
+        }
+
+        let mut search = Search {
+            rack_counts,
+            min_len,
+            max_len,
+            current: [0u8; 26],
+            visit: &mut visit,
+        };
+        self.walk(&mut search, &letters, 0, rack_letters, blanks);
+    }
+
+    /// Picks how many of each rack letter to use, then hands the rest to
+    /// blanks. `left` counts the rack letters not decided yet, so branches
+    /// that can no longer reach the shortest length are cut.
+    fn walk<F>(
+        &self,
+        search: &mut Search<'_, F>,
+        letters: &[usize],
+        used: usize,
+        left: usize,
+        blanks: usize,
+    ) where
+        F: FnMut(&str),
+    {
+        if used + left + blanks < search.min_len {
+            return;
+        }
+        let Some((&idx, rest)) = letters.split_first() else {
+            self.fill_blanks(search, 0, used, blanks);
+            return;
+        };
+        let in_rack = usize::from(search.rack_counts[idx]);
+        let available = in_rack.min(search.max_len - used);
+        for take in 0..=available {
+            search.current[idx] = take as u8;
+            self.walk(search, rest, used + take, left - in_rack, blanks);
+        }
+        search.current[idx] = 0;
+    }
+
+    /// Visits the current multiset, then extends it with blank letters in
+    /// non-decreasing order so no combination is tried twice.
+    fn fill_blanks<F>(&self, search: &mut Search<'_, F>, from: usize, used: usize, blanks: usize)
+    where
+        F: FnMut(&str),
+    {
+        if used + blanks < search.min_len {
+            return;
+        }
+        if used >= search.min_len {
+            if let Some(bucket) = self.buckets.get(&search.current) {
+                for &offset in bucket {
+                    (search.visit)(word_at(&self.text, offset, used));
+                }
+            }
+        }
+        if blanks == 0 || used == search.max_len {
+            return;
+        }
+        for idx in from..26 {
+            if search.current[idx] < search.rack_counts[idx] {
+                continue;
+            }
+            search.current[idx] += 1;
+            self.fill_blanks(search, idx, used + 1, blanks - 1);
+            search.current[idx] -= 1;
+        }
+    }
+}
+
+struct Search<'a, F> {
+    rack_counts: &'a Signature,
+    min_len: usize,
+    max_len: usize,
+    current: Signature,
+    visit: &'a mut F,
+}
+
+fn word_at(text: &str, offset: u32, len: usize) -> &str {
+    let start = offset as usize;
+    &text[start..start + len]
+}
+
+fn signature_len(signature: &Signature) -> usize {
+    signature.iter().map(|&count| usize::from(count)).sum()
+}
+
+pub fn signature_of(word: &str) -> Option<Signature> {
+    let mut counts = [0u8; 26];
+    for ch in word.chars() {
+        if !ch.is_ascii_uppercase() {
+            return None;
+        }
+        let idx = (ch as u8 - b'A') as usize;
+        counts[idx] = counts[idx].saturating_add(1);
+    }
+    Some(counts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn visits_only_words_the_rack_can_form() {
+        let index = AnagramIndex::new(["TEA", "EAT", "TEAS", "SEAT", "TAT", "AT"]);
+        let rack = signature_of("TAE").unwrap();
+
+        let mut seen: Vec<String> = Vec::new();
+        index.for_each_fit(&rack, 0, None, |word| seen.push(word.to_string()));
+        seen.sort();
+        assert_eq!(seen, vec!["AT", "EAT", "TEA"]);
+
+        let mut exact: Vec<String> = Vec::new();
+        index.for_each_fit(&rack, 0, Some(2), |word| exact.push(word.to_string()));
+        assert_eq!(exact, vec!["AT"]);
+
+        let mut wild: Vec<String> = Vec::new();
+        index.for_each_fit(&rack, 1, Some(4), |word| wild.push(word.to_string()));
+        wild.sort();
+        assert_eq!(wild, vec!["SEAT", "TEAS"]);
+
+        // A blank standing for a letter the rack already has would reach the
+        // same multiset twice.
+        let mut any: Vec<String> = Vec::new();
+        index.for_each_fit(&rack, 1, None, |word| any.push(word.to_string()));
+        any.sort();
+        assert_eq!(any, vec!["AT", "EAT", "SEAT", "TAT", "TEA", "TEAS"]);
+    }
+}
@@ -0,0 +1,177 @@
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::solver::LETTER_BAG_COUNTS;
+use crate::storage;
+
+/// How many observed tiles the standard bag is worth before any draws are logged.
+const PRIOR_WEIGHT: f64 = 100.0;
+/// Normal quantile for the reported 95% intervals.
+const Z_95: f64 = 1.96;
+
+/// Which letter distribution probability estimates draw from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LetterModel {
+    /// The fixed `LETTER_BAG_COUNTS` bag.
+    #[default]
+    Standard,
+    /// The standard bag updated with every logged draw.
+    Calibrated,
+}
+
+impl LetterModel {
+    pub fn by_name(name: &str) -> Option<Self> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "standard" => Some(Self::Standard),
+            "calibrated" => Some(Self::Calibrated),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Standard => "standard",
+            Self::Calibrated => "calibrated",
+        }
+    }
+}
+
+/// Letters seen in fresh racks and reroll results.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DrawTally {
+    /// Racks and rerolls logged.
+    pub draws: u32,
+    pub letters: [u32; 26],
+}
+
+impl DrawTally {
+    pub fn tiles(&self) -> u32 {
+        self.letters.iter().sum()
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct LetterEstimate {
+    pub letter: char,
+    pub observed: u32,
+    /// Share of the standard bag.
+    pub standard: f64,
+    /// Posterior mean share after the logged draws.
+    pub share: f64,
+    pub low: f64,
+    pub high: f64,
+}
+
+/// Per-letter shares from a Dirichlet prior on the standard bag, updated with
+/// the tally, each with a 95% interval from its beta marginal.
+pub fn estimate(tally: &DrawTally) -> Vec<LetterEstimate> {
+    let bag_total: f64 = LETTER_BAG_COUNTS
+        .iter()
+        .map(|&count| f64::from(count))
+        .sum();
+    let concentration = PRIOR_WEIGHT + f64::from(tally.tiles());
+    (0..26)
+        .map(|idx| {
+            let standard = f64::from(LETTER_BAG_COUNTS[idx]) / bag_total;
+            let observed = tally.letters[idx];
+            let share = (standard * PRIOR_WEIGHT + f64::from(observed)) / concentration;
+            let spread = Z_95 * (share * (1.0 - share) / (concentration + 1.0)).sqrt();
+            LetterEstimate {
+                letter: (b'A' + idx as u8) as char,
+                observed,
+                standard,
+                share,
+                low: (share - spread).max(0.0),
+                high: (share + spread).min(1.0),
+            }
+        })
+        .collect()
+}
+
+/// Tile counts for a bag the size of the standard one with the calibrated
+/// shares, so the draw-odds code can use it unchanged. Every letter keeps at
+/// least one tile.
+pub fn calibrated_bag(tally: &DrawTally) -> [u8; 26] {
+    let bag_total: f64 = LETTER_BAG_COUNTS
+        .iter()
+        .map(|&count| f64::from(count))
+        .sum();
+    let estimates = estimate(tally);
+    std::array::from_fn(|idx| (estimates[idx].share * bag_total).round().clamp(1.0, 255.0) as u8)
+}
+
+/// Logged draws, persisted as JSON in the app data dir.
+pub struct DrawLog {
+    path: PathBuf,
+    tally: DrawTally,
+}
+
+impl DrawLog {
+    pub fn open(path: PathBuf) -> Result<Self, String> {
+        let tally = match storage::read_optional(&path)? {
+            Some(text) => serde_json::from_str(&text)
+                .map_err(|err| format!("Failed to parse {}: {err}", path.display()))?,
+            None => DrawTally::default(),
+        };
+        Ok(Self { path, tally })
+    }
+
+    pub fn tally(&self) -> &DrawTally {
+        &self.tally
+    }
+
+    /// Logs the letters of one fresh rack or one reroll result. Kept tiles
+    /// were not drawn again and should be left out. The tally only changes
+    /// once it has been saved.
+    pub fn record(&mut self, letters: &[char]) -> Result<(), String> {
+        let mut tally = self.tally.clone();
+        for &ch in letters {
+            if ch.is_ascii_uppercase() {
+                let count = &mut tally.letters[(ch as u8 - b'A') as usize];
+                *count = count.saturating_add(1);
+            }
+        }
+        tally.draws = tally.draws.saturating_add(1);
+        let text = serde_json::to_string_pretty(&tally)
+            .map_err(|err| format!("Failed to encode letter draws: {err}"))?;
+        storage::write_atomic(&self.path, &text)?;
+        self.tally = tally;
+        Ok(())
+    }
+
+    pub fn bag(&self, model: LetterModel) -> [u8; 26] {
+        match model {
+            LetterModel::Standard => LETTER_BAG_COUNTS,
+            LetterModel::Calibrated => calibrated_bag(&self.tally),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn observed_draws_pull_the_bag_away_from_the_standard() {
+        let fresh = DrawTally::default();
+        assert_eq!(calibrated_bag(&fresh), LETTER_BAG_COUNTS);
+
+        let mut tally = DrawTally::default();
+        tally.letters[(b'Z' - b'A') as usize] = 60;
+        tally.letters[(b'E' - b'A') as usize] = 40;
+        tally.draws = 15;
+        let estimates = estimate(&tally);
+        let z = estimates[(b'Z' - b'A') as usize];
+        assert!(z.share > z.standard);
+        assert!(z.low < z.share && z.share < z.high);
+        let q = estimates[(b'Q' - b'A') as usize];
+        assert!(q.share < q.standard);
+
+        let bag = calibrated_bag(&tally);
+        assert!(bag[(b'Z' - b'A') as usize] > LETTER_BAG_COUNTS[(b'Z' - b'A') as usize]);
+        assert!(bag.iter().all(|&count| count >= 1));
+        assert!((estimates.iter().map(|e| e.share).sum::<f64>() - 1.0).abs() < 1e-9);
+    }
+}
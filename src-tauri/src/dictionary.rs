@@ -0,0 +1,373 @@
+use std::collections::{BTreeSet, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, OnceLock, PoisonError, RwLock};
+
+use fst::Set;
+
+use crate::anagram::{AnagramIndex, Signature};
+use crate::word_filter::{self, WordSet};
+
+static COMPILED_DICTIONARY: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/dictionary.fst"));
+
+/// The installed dictionary and the load generation it came from.
+static ACTIVE: RwLock<Option<(u64, Arc<Dictionary>)>> = RwLock::new(None);
+static GENERATION: AtomicU64 = AtomicU64::new(0);
+static BUNDLED_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+/// Returns the dictionary the solver should use right now, building the
+/// embedded one on first use if nothing has been installed yet.
+pub fn current() -> Arc<Dictionary> {
+    if let Some((_, active)) = ACTIVE
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .as_ref()
+    {
+        return active.clone();
+    }
+    ACTIVE
+        .write()
+        .unwrap_or_else(PoisonError::into_inner)
+        .get_or_insert_with(|| (0, Arc::new(Dictionary::embedded())))
+        .1
+        .clone()
+}
+
+/// Numbers a load before it starts, so `install` can tell which is newest.
+pub fn next_generation() -> u64 {
+    GENERATION.fetch_add(1, Ordering::Relaxed) + 1
+}
+
+/// Swaps in a freshly loaded dictionary unless one from a later load is
+/// already installed; solves already running keep the old one. Returns
+/// whether it was installed.
+pub fn install(generation: u64, dictionary: Dictionary) -> bool {
+    let mut active = ACTIVE.write().unwrap_or_else(PoisonError::into_inner);
+    if active
+        .as_ref()
+        .is_some_and(|(installed, _)| *installed > generation)
+    {
+        return false;
+    }
+    *active = Some((generation, Arc::new(dictionary)));
+    true
+}
+
+/// Where the app bundle keeps the source word lists. Only the compiled
+/// dictionary is embedded; the lists themselves are read from here when a
+/// word is explained or the dictionary is rebuilt.
+pub fn set_bundled_dir(dir: PathBuf) {
+    let _ = BUNDLED_DIR.set(dir);
+}
+
+/// Errors when the app could not locate its resources. Tests read the
+/// lists straight from the source tree instead.
+fn bundled_dir() -> Result<&'static Path, String> {
+    #[cfg(test)]
+    BUNDLED_DIR.get_or_init(|| Path::new(env!("CARGO_MANIFEST_DIR")).join("src/data"));
+    BUNDLED_DIR
+        .get()
+        .map(PathBuf::as_path)
+        .ok_or_else(|| "The bundled word lists could not be located.".to_string())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SourceKind {
+    Wordlist,
+    CoreWords,
+    CspellWords,
+    Blocklist,
+}
+
+impl SourceKind {
+    pub const ALL: [SourceKind; 4] = [
+        SourceKind::Wordlist,
+        SourceKind::CoreWords,
+        SourceKind::CspellWords,
+        SourceKind::Blocklist,
+    ];
+
+    pub fn as_code(self) -> &'static str {
+        match self {
+            SourceKind::Wordlist => "wordlist",
+            SourceKind::CoreWords => "core_words",
+            SourceKind::CspellWords => "cspell_words",
+            SourceKind::Blocklist => "blocklist",
+        }
+    }
+
+    pub fn file_name(self) -> &'static str {
+        match self {
+            SourceKind::Wordlist => "wordlist.txt",
+            SourceKind::CoreWords => "core_words.txt",
+            SourceKind::CspellWords => "cspell-words.txt",
+            SourceKind::Blocklist => "invalid-words.txt",
+        }
+    }
+}
+
+/// One source word list, read from the app data dir when the user put a
+/// copy there and from the bundled copy otherwise.
+pub struct WordSource {
+    path: PathBuf,
+    runtime: bool,
+    words: HashSet<String>,
+}
+
+impl WordSource {
+    fn load(kind: SourceKind, runtime_dir: Option<&Path>) -> Result<Self, String> {
+        let override_path = runtime_dir
+            .map(|dir| dir.join(kind.file_name()))
+            .filter(|path| path.is_file());
+        let runtime = override_path.is_some();
+        let path = match override_path {
+            Some(path) => path,
+            None => bundled_dir()?.join(kind.file_name()),
+        };
+        let text = fs::read_to_string(&path)
+            .map_err(|err| format!("Failed to read {}: {err}", path.display()))?;
+        Ok(Self {
+            words: word_filter::parse_word_list(&text).collect(),
+            path,
+            runtime,
+        })
+    }
+
+    pub fn word_count(&self) -> usize {
+        self.words.len()
+    }
+
+    pub fn is_runtime(&self) -> bool {
+        self.runtime
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl WordSet for WordSource {
+    fn contains_word(&self, word: &str) -> bool {
+        self.words.contains(word)
+    }
+}
+
+/// The four lists the dictionary is filtered from.
+pub struct Sources {
+    wordlist: WordSource,
+    core_words: WordSource,
+    cspell_words: WordSource,
+    blocklist: WordSource,
+}
+
+impl Sources {
+    fn load(runtime_dir: Option<&Path>) -> Result<Self, String> {
+        Ok(Self {
+            wordlist: WordSource::load(SourceKind::Wordlist, runtime_dir)?,
+            core_words: WordSource::load(SourceKind::CoreWords, runtime_dir)?,
+            cspell_words: WordSource::load(SourceKind::CspellWords, runtime_dir)?,
+            blocklist: WordSource::load(SourceKind::Blocklist, runtime_dir)?,
+        })
+    }
+
+    pub fn get(&self, kind: SourceKind) -> &WordSource {
+        match kind {
+            SourceKind::Wordlist => &self.wordlist,
+            SourceKind::CoreWords => &self.core_words,
+            SourceKind::CspellWords => &self.cspell_words,
+            SourceKind::Blocklist => &self.blocklist,
+        }
+    }
+
+    fn filtered_words(&self) -> Vec<String> {
+        self.wordlist
+            .words
+            .iter()
+            .filter(|word| {
+                word_filter::first_rejection(
+                    word,
+                    &self.core_words,
+                    &self.cspell_words,
+                    &self.blocklist,
+                )
+                .is_none()
+            })
+            .cloned()
+            .collect()
+    }
+}
+
+/// The solver's word list plus the sources it was filtered from, which are
+/// only read once something asks for them.
+pub struct Dictionary {
+    runtime_dir: Option<PathBuf>,
+    sources: OnceLock<Result<Sources, String>>,
+    allowlisted: HashSet<String>,
+    index: AnagramIndex,
+}
+
+impl Dictionary {
+    /// Uses the word list compiled by `build.rs` without re-running the filters.
+    pub fn embedded() -> Self {
+        Self::from_parts(None, OnceLock::new(), compiled_words(), &BTreeSet::new())
+    }
+
+    /// Re-filters the source lists when any of them has a copy in `dir`, and
+    /// uses the compiled word list otherwise. Allowlisted words skip the
+    /// dictionary filters but must still have a playable length and alphabet.
+    pub fn load(dir: &Path, allowlist: &BTreeSet<String>) -> Result<Self, String> {
+        let overridden = SourceKind::ALL
+            .iter()
+            .any(|kind| dir.join(kind.file_name()).is_file());
+        let sources = OnceLock::new();
+        let words = if overridden {
+            let loaded = Sources::load(Some(dir))?;
+            let words = loaded.filtered_words();
+            let _ = sources.set(Ok(loaded));
+            words
+        } else {
+            compiled_words()
+        };
+        Ok(Self::from_parts(
+            Some(dir.to_path_buf()),
+            sources,
+            words,
+            allowlist,
+        ))
+    }
+
+    fn from_parts(
+        runtime_dir: Option<PathBuf>,
+        sources: OnceLock<Result<Sources, String>>,
+        mut words: Vec<String>,
+        allowlist: &BTreeSet<String>,
+    ) -> Self {
+        words.sort_unstable();
+        let allowlisted: HashSet<String> = allowlist
+            .iter()
+            .filter(|word| word_filter::has_valid_shape(word))
+            .filter(|word| words.binary_search(word).is_err())
+            .cloned()
+            .collect();
+        words.extend(allowlisted.iter().cloned());
+
+        Self {
+            runtime_dir,
+            sources,
+            allowlisted,
+            index: AnagramIndex::new(words),
+        }
+    }
+
+    /// The source lists, read from disk the first time they are needed.
+    pub fn sources(&self) -> Result<&Sources, String> {
+        self.sources
+            .get_or_init(|| Sources::load(self.runtime_dir.as_deref()))
+            .as_ref()
+            .map_err(Clone::clone)
+    }
+
+    /// One source list for callers that can't report a read failure; a list
+    /// that can't be read counts as empty.
+    pub fn source(&self, kind: SourceKind) -> &WordSource {
+        static UNREADABLE: OnceLock<WordSource> = OnceLock::new();
+        match self.sources() {
+            Ok(sources) => sources.get(kind),
+            Err(_) => UNREADABLE.get_or_init(|| WordSource {
+                path: PathBuf::new(),
+                runtime: false,
+                words: HashSet::new(),
+            }),
+        }
+    }
+
+    pub fn word_count(&self) -> usize {
+        self.index.word_count()
+    }
+
+    pub fn allowlisted_count(&self) -> usize {
+        self.allowlisted.len()
+    }
+
+    pub fn contains(&self, word: &str) -> bool {
+        self.index.contains(word)
+    }
+
+    /// True when `word` is only in the dictionary because the user allowlisted it.
+    pub fn is_allowlisted(&self, word: &str) -> bool {
+        self.allowlisted.contains(word)
+    }
+
+    pub fn for_each_fit<F>(
+        &self,
+        rack_counts: &Signature,
+        blanks: usize,
+        length: Option<usize>,
+        visit: F,
+    ) where
+        F: FnMut(&str),
+    {
+        self.index.for_each_fit(rack_counts, blanks, length, visit);
+    }
+
+    #[cfg(test)]
+    pub fn words(&self) -> impl Iterator<Item = &str> {
+        self.index.words()
+    }
+}
+
+fn compiled_words() -> Vec<String> {
+    Set::new(COMPILED_DICTIONARY)
+        .expect("the compiled dictionary is a valid FST")
+        .stream()
+        .into_strs()
+        .expect("the compiled dictionary holds ASCII words")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn runtime_lists_override_embedded_sources() {
+        let dir = std::env::temp_dir().join(format!("yatzy-dictionary-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("wordlist.txt"), "zebra\nquartz\nbcd\nqi\n").unwrap();
+
+        let dictionary = Dictionary::load(&dir, &BTreeSet::new()).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        let sources = dictionary.sources().unwrap();
+        assert!(sources.get(SourceKind::Wordlist).is_runtime());
+        assert!(!sources.get(SourceKind::CoreWords).is_runtime());
+        assert_eq!(sources.get(SourceKind::Wordlist).word_count(), 4);
+
+        // BCD has no vowel and QI is missing from the bundled core words.
+        let mut words: Vec<&str> = dictionary.words().collect();
+        words.sort_unstable();
+        assert_eq!(words, vec!["QUARTZ", "ZEBRA"]);
+    }
+
+    #[test]
+    fn allowlist_bypasses_filters_but_not_shape_checks() {
+        let dir = std::env::temp_dir().join("yatzy-dictionary-missing");
+        let allowlist: BTreeSet<String> = ["QI", "ZEBRA", "X", "HMM", "AAA"]
+            .iter()
+            .map(|word| word.to_string())
+            .collect();
+
+        let dictionary = Dictionary::load(&dir, &allowlist).unwrap();
+        // Nothing overrides the bundled lists, so they stay unread.
+        assert!(dictionary.sources.get().is_none());
+
+        // HMM and AAA fail the vowel and repeated-letter filters, which the
+        // allowlist exists to override.
+        assert!(dictionary.is_allowlisted("QI"));
+        assert!(dictionary.is_allowlisted("HMM"));
+        assert!(dictionary.is_allowlisted("AAA"));
+        assert!(!dictionary.is_allowlisted("ZEBRA"));
+        assert!(!dictionary.is_allowlisted("X"));
+        assert_eq!(dictionary.allowlisted_count(), 3);
+    }
+}
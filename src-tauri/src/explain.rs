@@ -0,0 +1,220 @@
+use std::collections::{BTreeSet, HashSet};
+
+use crate::dictionary::{Dictionary, SourceKind};
+use crate::scoring::ScoreContext;
+use crate::solver;
+use crate::word_filter::{self, Rejection, WordSet};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Exclusion {
+    NotInWordlist,
+    NotInCoreWords,
+    NotInCspell,
+    NoVowel,
+    UniformCharacters,
+    NotAlphabetic,
+    Blocklisted,
+    InvalidForRequest,
+    WrongLength,
+    DoesNotFitRack,
+}
+
+impl Exclusion {
+    pub fn as_code(self) -> &'static str {
+        match self {
+            Exclusion::NotInWordlist => "not_in_wordlist",
+            Exclusion::NotInCoreWords => "not_in_core_words",
+            Exclusion::NotInCspell => "not_in_cspell",
+            Exclusion::NoVowel => "no_vowel",
+            Exclusion::UniformCharacters => "uniform_characters",
+            Exclusion::NotAlphabetic => "not_alphabetic",
+            Exclusion::Blocklisted => "blocklisted",
+            Exclusion::InvalidForRequest => "invalid_for_request",
+            Exclusion::WrongLength => "wrong_length",
+            Exclusion::DoesNotFitRack => "does_not_fit_rack",
+        }
+    }
+
+    pub fn describe(self) -> &'static str {
+        match self {
+            Exclusion::NotInWordlist => "Missing from wordlist.txt",
+            Exclusion::NotInCoreWords => "Missing from core_words.txt",
+            Exclusion::NotInCspell => "Missing from cspell-words.txt",
+            Exclusion::NoVowel => "Has no vowel (A/E/I/O/U/Y)",
+            Exclusion::UniformCharacters => "Repeats a single letter",
+            Exclusion::NotAlphabetic => "Contains characters other than A–Z",
+            Exclusion::Blocklisted => "On the blocklist",
+            Exclusion::InvalidForRequest => "Marked invalid in this request",
+            Exclusion::WrongLength => "Length is outside 2–15 or misses the target length",
+            Exclusion::DoesNotFitRack => "Needs letters the rack does not have",
+        }
+    }
+}
+
+impl From<Rejection> for Exclusion {
+    fn from(rejection: Rejection) -> Self {
+        match rejection {
+            Rejection::NotInCoreWords => Exclusion::NotInCoreWords,
+            Rejection::WrongLength => Exclusion::WrongLength,
+            Rejection::NotAlphabetic => Exclusion::NotAlphabetic,
+            Rejection::NotInCspell => Exclusion::NotInCspell,
+            Rejection::NoVowel => Exclusion::NoVowel,
+            Rejection::UniformCharacters => Exclusion::UniformCharacters,
+            Rejection::Blocklisted => Exclusion::Blocklisted,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Explanation {
+    pub in_dictionary: bool,
+    pub from_allowlist: bool,
+    pub exclusions: Vec<Exclusion>,
+    pub score: Option<u32>,
+}
+
+/// Walks `word` through the same filters as `Dictionary::load` and
+/// `solve_rack` and records every one that would drop it. Reads the source
+/// lists from disk if nothing has needed them yet.
+pub fn explain_word(
+    dictionary: &Dictionary,
+    word: &str,
+    tiles: &[String],
+    target_length: Option<usize>,
+    request_invalid: &HashSet<String>,
+    user_blocklist: &BTreeSet<String>,
+    scoring: &ScoreContext,
+) -> Result<Explanation, String> {
+    let in_dictionary = dictionary.contains(word);
+    let from_allowlist = dictionary.is_allowlisted(word);
+    let mut exclusions: Vec<Exclusion> = Vec::new();
+
+    if !from_allowlist {
+        let sources = dictionary.sources()?;
+        if !sources.get(SourceKind::Wordlist).contains_word(word) {
+            exclusions.push(Exclusion::NotInWordlist);
+        }
+        exclusions.extend(
+            word_filter::rejections(
+                word,
+                sources.get(SourceKind::CoreWords),
+                sources.get(SourceKind::CspellWords),
+                sources.get(SourceKind::Blocklist),
+            )
+            .into_iter()
+            .map(Exclusion::from),
+        );
+    } else if !word_filter::has_valid_shape(word) {
+        exclusions.push(Exclusion::WrongLength);
+    }
+
+    if user_blocklist.contains(word) {
+        push_unique(&mut exclusions, Exclusion::Blocklisted);
+    }
+    if request_invalid.contains(word) {
+        exclusions.push(Exclusion::InvalidForRequest);
+    }
+    let layout = if tiles.is_empty() {
+        None
+    } else {
+        solver::score_on_rack(word, tiles, scoring)
+    };
+    let squares = layout
+        .as_ref()
+        .map_or(word.len(), |breakdown| breakdown.tiles.len());
+    if target_length.is_some_and(|len| squares != len) {
+        push_unique(&mut exclusions, Exclusion::WrongLength);
+    }
+    if !tiles.is_empty() && layout.is_none() {
+        exclusions.push(Exclusion::DoesNotFitRack);
+    }
+    let score = match layout {
+        Some(breakdown) => Some(breakdown.total),
+        None => solver::score_word_with_bonuses(word, scoring),
+    };
+
+    Ok(Explanation {
+        in_dictionary,
+        from_allowlist,
+        exclusions,
+        score,
+    })
+}
+
+fn push_unique(exclusions: &mut Vec<Exclusion>, exclusion: Exclusion) {
+    if !exclusions.contains(&exclusion) {
+        exclusions.push(exclusion);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dictionary;
+    use crate::scoring::{Bonus, SCRABBLE};
+
+    #[test]
+    fn reports_dictionary_and_rack_filters() {
+        let dictionary = dictionary::current();
+        let letters: Vec<String> = "QIABCDE".chars().map(String::from).collect();
+        let blocklist: BTreeSet<String> = BTreeSet::new();
+        let invalid: HashSet<String> = ["BADE".to_string()].into_iter().collect();
+        let plain = ScoreContext {
+            profile: &SCRABBLE,
+            bonuses: &[],
+            round: 1,
+            slot_length: None,
+        };
+
+        let qi = explain_word(
+            &dictionary,
+            "QI",
+            &letters,
+            None,
+            &invalid,
+            &blocklist,
+            &ScoreContext {
+                profile: &SCRABBLE,
+                bonuses: &[Bonus::TripleLetter],
+                round: 2,
+                slot_length: None,
+            },
+        )
+        .unwrap();
+        assert!(!qi.in_dictionary);
+        assert_eq!(
+            qi.exclusions,
+            vec![Exclusion::NotInWordlist, Exclusion::NotInCoreWords]
+        );
+        assert_eq!(qi.score, Some(62));
+
+        let bade = explain_word(
+            &dictionary,
+            "BADE",
+            &letters,
+            Some(5),
+            &invalid,
+            &blocklist,
+            &plain,
+        )
+        .unwrap();
+        assert!(bade.in_dictionary);
+        assert_eq!(
+            bade.exclusions,
+            vec![Exclusion::InvalidForRequest, Exclusion::WrongLength]
+        );
+
+        let hmm = explain_word(
+            &dictionary,
+            "HMM",
+            &letters,
+            None,
+            &invalid,
+            &blocklist,
+            &plain,
+        )
+        .unwrap();
+        assert!(hmm.exclusions.contains(&Exclusion::NoVowel));
+        assert!(hmm.exclusions.contains(&Exclusion::DoesNotFitRack));
+    }
+}
@@ -0,0 +1,215 @@
+use crate::scoring::{Bonus, ScoreContext, ScoringProfile};
+use crate::solver;
+
+pub const ROUNDS: usize = 5;
+pub const SLOTS_PER_ROUND: usize = 5;
+/// Added once for every round whose five slots are all filled.
+pub const COMPLETION_BONUS: u32 = 100;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlayedWord {
+    pub word: String,
+    pub bonuses: Vec<Bonus>,
+    pub score: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RoundScore {
+    pub round: u8,
+    pub multiplier: u32,
+    pub slot_scores: [Option<u32>; SLOTS_PER_ROUND],
+    pub words_total: u32,
+    pub completion_bonus: u32,
+    pub total: u32,
+}
+
+/// Where and from what a word is played.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Placement<'a> {
+    pub bonuses: &'a [Bonus],
+    pub slot_length: Option<usize>,
+    /// 0-based square the word starts on; the best-scoring one when unset.
+    pub slot_offset: Option<usize>,
+    /// Rack the word was played from. Without one every letter is its own
+    /// plain tile.
+    pub rack: Option<&'a [String]>,
+}
+
+/// Words played in each slot of a five-round match, scored with one profile.
+/// Rounds are 1-based like `SolveRackRequest.round`; slots are 0-based indices.
+#[derive(Debug, Clone)]
+pub struct Scorecard {
+    profile: &'static ScoringProfile,
+    slots: [[Option<PlayedWord>; SLOTS_PER_ROUND]; ROUNDS],
+}
+
+impl Scorecard {
+    pub fn new(profile: &'static ScoringProfile) -> Self {
+        Self {
+            profile,
+            slots: Default::default(),
+        }
+    }
+
+    pub fn profile(&self) -> &'static ScoringProfile {
+        self.profile
+    }
+
+    /// Scores `word` on the slot's bonus squares, laid out from the rack the
+    /// best way, and records it, replacing anything already played there.
+    pub fn play(
+        &mut self,
+        round: u8,
+        slot: usize,
+        word: &str,
+        placement: &Placement,
+    ) -> Result<u32, String> {
+        let (round_idx, slot) = check_position(round, slot)?;
+        let word = word.trim().to_ascii_uppercase();
+        if word.is_empty() || !word.chars().all(|ch| ch.is_ascii_uppercase()) {
+            return Err(format!("'{word}' can't be scored; use letters A–Z only."));
+        }
+        let letters: Vec<String>;
+        let rack = match placement.rack {
+            Some(rack) => rack,
+            None => {
+                letters = word.chars().map(String::from).collect();
+                &letters
+            }
+        };
+        let scoring = ScoreContext {
+            profile: self.profile,
+            bonuses: placement.bonuses,
+            round,
+            slot_length: placement.slot_length,
+        };
+        let Some(breakdown) =
+            solver::score_on_rack_at(&word, rack, &scoring, placement.slot_offset)
+        else {
+            let unbounded = ScoreContext {
+                slot_length: None,
+                ..scoring
+            };
+            return Err(
+                if solver::score_on_rack(&word, rack, &unbounded).is_some() {
+                    format!("'{word}' doesn't fit slot {slot}.")
+                } else {
+                    format!("'{word}' can't be made from the rack {}.", rack.concat())
+                },
+            );
+        };
+        let score = breakdown.total;
+        self.slots[round_idx][slot] = Some(PlayedWord {
+            word,
+            bonuses: placement.bonuses.to_vec(),
+            score,
+        });
+        Ok(score)
+    }
+
+    pub fn slot(&self, round: u8, slot: usize) -> Option<&PlayedWord> {
+        let (round_idx, slot) = check_position(round, slot).ok()?;
+        self.slots[round_idx][slot].as_ref()
+    }
+
+    pub fn round_score(&self, round: u8) -> RoundScore {
+        let round_idx = usize::from(round.clamp(1, ROUNDS as u8) - 1);
+        let slot_scores = self.slots[round_idx]
+            .each_ref()
+            .map(|played| played.as_ref().map(|played| played.score));
+        let words_total = slot_scores.iter().flatten().sum();
+        let completion_bonus = if slot_scores.iter().all(Option::is_some) {
+            COMPLETION_BONUS
+        } else {
+            0
+        };
+        RoundScore {
+            round: round_idx as u8 + 1,
+            multiplier: self.profile.round_multiplier(round_idx as u8 + 1),
+            slot_scores,
+            words_total,
+            completion_bonus,
+            total: words_total + completion_bonus,
+        }
+    }
+
+    pub fn rounds(&self) -> Vec<RoundScore> {
+        (1..=ROUNDS as u8)
+            .map(|round| self.round_score(round))
+            .collect()
+    }
+
+    pub fn total(&self) -> u32 {
+        self.rounds().iter().map(|round| round.total).sum()
+    }
+}
+
+fn check_position(round: u8, slot: usize) -> Result<(usize, usize), String> {
+    if !(1..=ROUNDS as u8).contains(&round) {
+        return Err(format!("Round must be between 1 and {ROUNDS}."));
+    }
+    if slot >= SLOTS_PER_ROUND {
+        return Err(format!(
+            "Slot index must be between 0 and {}.",
+            SLOTS_PER_ROUND - 1
+        ));
+    }
+    Ok((usize::from(round - 1), slot))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scoring::SCRABBLE;
+
+    #[test]
+    fn completed_rounds_earn_the_bonus_once() {
+        let mut card = Scorecard::new(&SCRABBLE);
+        // YatzyRules.md worked example: QAT, Q on TL, DW, round 5.
+        let qat = card
+            .play(
+                5,
+                0,
+                "qat",
+                &Placement {
+                    bonuses: &[Bonus::TripleLetter, Bonus::None, Bonus::DoubleWord],
+                    ..Placement::default()
+                },
+            )
+            .unwrap();
+        assert_eq!(qat, 320);
+
+        for slot in 0..SLOTS_PER_ROUND {
+            card.play(1, slot, "AT", &Placement::default()).unwrap();
+        }
+        let first = card.round_score(1);
+        assert_eq!(first.words_total, 10);
+        assert_eq!(first.completion_bonus, COMPLETION_BONUS);
+        assert_eq!(card.round_score(5).completion_bonus, 0);
+        assert_eq!(card.total(), 320 + 110);
+
+        assert!(card.play(6, 0, "AT", &Placement::default()).is_err());
+        assert!(card.play(1, 5, "AT", &Placement::default()).is_err());
+        assert!(card.play(1, 0, "A-T", &Placement::default()).is_err());
+
+        // From a rack the blank scores nothing and QU is one tile, so QUIT
+        // fits three squares; the start square can be pinned.
+        let rack: Vec<String> = ["QU", "I", "?"].map(String::from).to_vec();
+        let from_rack = |offset| Placement {
+            bonuses: &[Bonus::None, Bonus::None, Bonus::None, Bonus::DoubleWord],
+            slot_length: Some(4),
+            slot_offset: offset,
+            rack: Some(&rack),
+        };
+        assert_eq!(
+            card.play(2, 0, "quit", &from_rack(None)).unwrap(),
+            2 * 2 * 12
+        );
+        assert_eq!(
+            card.play(2, 0, "quit", &from_rack(Some(0))).unwrap(),
+            2 * 12
+        );
+        assert!(card.play(2, 0, "quit", &from_rack(Some(2))).is_err());
+        assert!(card.play(2, 0, "quilt", &from_rack(None)).is_err());
+    }
+}
@@ -0,0 +1,497 @@
+use serde::{Deserialize, Serialize};
+
+use crate::game::{Placement, Scorecard, ROUNDS};
+use crate::history::{GameRecord, RoundRecord, SlotRecord};
+use crate::scoring::{Bonus, ScoringProfile};
+
+/// Identifies an exported game log; see `docs/GameLogFormat.md`.
+pub const FORMAT: &str = "yatzy-coach-game-log";
+/// Bumped whenever a field changes meaning or goes away.
+pub const VERSION: u32 = 1;
+
+const CSV_COLUMNS: [&str; 15] = [
+    "version",
+    "game_id",
+    "player",
+    "finished_at",
+    "scoring_profile",
+    "total",
+    "round",
+    "slot_index",
+    "rack_letters",
+    "earlier_racks",
+    "rack_bonuses",
+    "slot_length",
+    "word",
+    "score",
+    "skipped",
+];
+
+/// One slot, named after the `SolveRackRequest` and `WordRecommendation`
+/// fields it corresponds to.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, rename_all = "snake_case")]
+pub struct LoggedSlot {
+    pub round: u8,
+    pub slot_index: u8,
+    /// Rack the word was played from.
+    pub rack_letters: Vec<String>,
+    /// Fresh rack and reroll results before `rack_letters`, oldest first.
+    pub earlier_racks: Vec<Vec<String>>,
+    pub rack_bonuses: Vec<String>,
+    pub slot_length: Option<u8>,
+    pub word: Option<String>,
+    pub score: Option<u32>,
+    pub skipped: bool,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, rename_all = "snake_case")]
+pub struct LoggedGame {
+    pub game_id: u64,
+    pub player: String,
+    pub finished_at: u64,
+    pub scoring_profile: String,
+    pub slots: Vec<LoggedSlot>,
+    /// Final score including completion bonuses.
+    pub total: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct LogDocument {
+    pub format: String,
+    pub version: u32,
+    #[serde(default)]
+    pub games: Vec<LoggedGame>,
+}
+
+impl LoggedGame {
+    pub fn from_record(record: &GameRecord) -> Self {
+        Self {
+            game_id: record.id,
+            player: record.player.clone(),
+            finished_at: record.finished_at,
+            scoring_profile: record.scoring_profile.clone(),
+            slots: record
+                .slots
+                .iter()
+                .map(|slot| {
+                    let (rack_letters, earlier_racks) = match slot.racks.split_last() {
+                        Some((last, earlier)) => (last.clone(), earlier.to_vec()),
+                        None => (Vec::new(), Vec::new()),
+                    };
+                    LoggedSlot {
+                        round: slot.round,
+                        slot_index: slot.slot_index,
+                        rack_letters,
+                        earlier_racks,
+                        rack_bonuses: slot.bonuses.clone(),
+                        slot_length: slot.slot_length,
+                        word: slot.word.clone(),
+                        score: slot.score,
+                        skipped: slot.skipped,
+                    }
+                })
+                .collect(),
+            total: record.total,
+        }
+    }
+
+    /// Rebuilds the history record, rescoring every word so a log whose
+    /// scores don't match the scoring engine is rejected.
+    pub fn into_record(self) -> Result<GameRecord, String> {
+        let profile = ScoringProfile::by_name(&self.scoring_profile).ok_or_else(|| {
+            format!(
+                "Game {} uses unknown scoring profile '{}'.",
+                self.game_id, self.scoring_profile
+            )
+        })?;
+        let mut card = Scorecard::new(profile);
+        let mut slots = Vec::with_capacity(self.slots.len());
+        for slot in self.slots {
+            let mut racks = slot.earlier_racks;
+            if !slot.rack_letters.is_empty() {
+                racks.push(slot.rack_letters);
+            }
+            let mut played = None;
+            if let Some(word) = &slot.word {
+                let bonuses: Vec<Bonus> = slot
+                    .rack_bonuses
+                    .iter()
+                    .map(|value| Bonus::from_str_raw(value))
+                    .collect();
+                let placement = |slot_offset| Placement {
+                    bonuses: &bonuses,
+                    slot_length: slot.slot_length.map(usize::from),
+                    slot_offset,
+                    rack: racks.last().map(Vec::as_slice),
+                };
+                let slot_index = usize::from(slot.slot_index);
+                let mut score = card.play(slot.round, slot_index, word, &placement(None))?;
+                // The log doesn't say which square the word started on, so a
+                // lower listed score may be the same word placed elsewhere.
+                if let Some(listed) = slot.score.filter(|&listed| listed != score) {
+                    let squares = slot.slot_length.map_or(1, usize::from);
+                    if (0..squares).any(|offset| {
+                        card.play(slot.round, slot_index, word, &placement(Some(offset)))
+                            == Ok(listed)
+                    }) {
+                        score = listed;
+                    }
+                }
+                if slot.score.is_some_and(|listed| listed != score) {
+                    return Err(format!(
+                        "Game {}: '{}' in round {} slot {} scores {score}, not {}.",
+                        self.game_id,
+                        word.trim(),
+                        slot.round,
+                        slot.slot_index,
+                        slot.score.unwrap_or_default()
+                    ));
+                }
+                played = card.slot(slot.round, usize::from(slot.slot_index));
+            }
+            slots.push(SlotRecord {
+                round: slot.round,
+                slot_index: slot.slot_index,
+                bonuses: slot.rack_bonuses,
+                slot_length: slot.slot_length,
+                racks,
+                word: played.map(|played| played.word.clone()),
+                score: played.map(|played| played.score),
+                skipped: slot.skipped,
+            });
+        }
+        if card.total() != self.total {
+            return Err(format!(
+                "Game {} totals {}, not {}.",
+                self.game_id,
+                card.total(),
+                self.total
+            ));
+        }
+        let reached = slots.iter().map(|slot| slot.round).max().unwrap_or(1);
+        let rounds = (1..=reached.min(ROUNDS as u8))
+            .map(|round| {
+                let score = card.round_score(round);
+                RoundRecord {
+                    round,
+                    multiplier: score.multiplier,
+                    words_total: score.words_total,
+                    completion_bonus: score.completion_bonus,
+                    total: score.total,
+                }
+            })
+            .collect();
+        Ok(GameRecord {
+            id: self.game_id,
+            player: self.player,
+            finished_at: self.finished_at,
+            scoring_profile: profile.name.to_string(),
+            rounds,
+            slots,
+            total: card.total(),
+        })
+    }
+}
+
+pub fn to_json(records: &[&GameRecord]) -> Result<String, String> {
+    let document = LogDocument {
+        format: FORMAT.to_string(),
+        version: VERSION,
+        games: records
+            .iter()
+            .map(|record| LoggedGame::from_record(record))
+            .collect(),
+    };
+    serde_json::to_string_pretty(&document)
+        .map_err(|err| format!("Failed to encode game log: {err}"))
+}
+
+pub fn from_json(text: &str) -> Result<Vec<GameRecord>, String> {
+    let document: LogDocument =
+        serde_json::from_str(text).map_err(|err| format!("Failed to parse game log: {err}"))?;
+    if document.format != FORMAT {
+        return Err(format!("Not a game log: format is '{}'.", document.format));
+    }
+    check_version(document.version)?;
+    document
+        .games
+        .into_iter()
+        .map(LoggedGame::into_record)
+        .collect()
+}
+
+/// One row per slot, with the game's fields repeated on each. Tiles are
+/// separated by spaces and earlier racks by `|`.
+pub fn to_csv(records: &[&GameRecord]) -> String {
+    let mut out = CSV_COLUMNS.join(",");
+    out.push('\n');
+    for record in records {
+        let game = LoggedGame::from_record(record);
+        for slot in &game.slots {
+            let fields = [
+                VERSION.to_string(),
+                game.game_id.to_string(),
+                game.player.clone(),
+                game.finished_at.to_string(),
+                game.scoring_profile.clone(),
+                game.total.to_string(),
+                slot.round.to_string(),
+                slot.slot_index.to_string(),
+                slot.rack_letters.join(" "),
+                slot.earlier_racks
+                    .iter()
+                    .map(|rack| rack.join(" "))
+                    .collect::<Vec<_>>()
+                    .join("|"),
+                slot.rack_bonuses.join(" "),
+                slot.slot_length
+                    .map(|len| len.to_string())
+                    .unwrap_or_default(),
+                slot.word.clone().unwrap_or_default(),
+                slot.score
+                    .map(|score| score.to_string())
+                    .unwrap_or_default(),
+                slot.skipped.to_string(),
+            ];
+            let row: Vec<String> = fields.iter().map(|field| csv_field(field)).collect();
+            out.push_str(&row.join(","));
+            out.push('\n');
+        }
+    }
+    out
+}
+
+/// Reads rows written by `to_csv`, grouping them into games by `game_id`.
+/// Rows of one game must agree on every game-level column.
+pub fn from_csv(text: &str) -> Result<Vec<GameRecord>, String> {
+    let mut records = csv_records(text)?.into_iter();
+    let (_, header) = records.next().ok_or("The game log is empty.")?;
+    if header != CSV_COLUMNS {
+        return Err(format!(
+            "Game log columns must be: {}.",
+            CSV_COLUMNS.join(",")
+        ));
+    }
+    let mut games: Vec<LoggedGame> = Vec::new();
+    for (line_no, row) in records {
+        if row.len() != CSV_COLUMNS.len() {
+            return Err(format!(
+                "Line {line_no} has {} fields, not {}.",
+                row.len(),
+                CSV_COLUMNS.len()
+            ));
+        }
+        let field = |column: usize| row[column].as_str();
+        check_version(csv_number(&row, 0, line_no)?)?;
+        let tiles =
+            |text: &str| -> Vec<String> { text.split_whitespace().map(String::from).collect() };
+        let slot = LoggedSlot {
+            round: csv_number(&row, 6, line_no)?,
+            slot_index: csv_number(&row, 7, line_no)?,
+            rack_letters: tiles(field(8)),
+            earlier_racks: field(9)
+                .split('|')
+                .map(tiles)
+                .filter(|rack| !rack.is_empty())
+                .collect(),
+            rack_bonuses: tiles(field(10)),
+            slot_length: (!field(11).is_empty())
+                .then(|| csv_number(&row, 11, line_no))
+                .transpose()?,
+            word: Some(field(12).to_string()).filter(|word| !word.is_empty()),
+            score: (!field(13).is_empty())
+                .then(|| csv_number(&row, 13, line_no))
+                .transpose()?,
+            skipped: field(14).trim().eq_ignore_ascii_case("true"),
+        };
+        let game = LoggedGame {
+            game_id: csv_number(&row, 1, line_no)?,
+            player: field(2).to_string(),
+            finished_at: csv_number(&row, 3, line_no)?,
+            scoring_profile: field(4).to_string(),
+            slots: vec![slot],
+            total: csv_number(&row, 5, line_no)?,
+        };
+        match games
+            .iter_mut()
+            .find(|logged| logged.game_id == game.game_id)
+        {
+            Some(logged) => {
+                let differs = [
+                    (2, logged.player != game.player),
+                    (3, logged.finished_at != game.finished_at),
+                    (4, logged.scoring_profile != game.scoring_profile),
+                    (5, logged.total != game.total),
+                ]
+                .into_iter()
+                .find_map(|(column, differs)| differs.then_some(column));
+                if let Some(column) = differs {
+                    return Err(format!(
+                        "Line {line_no}: '{}' differs from earlier rows of game {}.",
+                        CSV_COLUMNS[column], game.game_id
+                    ));
+                }
+                logged.slots.extend(game.slots);
+            }
+            None => games.push(game),
+        }
+    }
+    games.into_iter().map(LoggedGame::into_record).collect()
+}
+
+/// Parses a numeric column straight into its type, so values out of range
+/// are rejected rather than wrapped.
+fn csv_number<T: std::str::FromStr>(
+    row: &[String],
+    column: usize,
+    line_no: usize,
+) -> Result<T, String> {
+    row[column].trim().parse().map_err(|_| {
+        format!(
+            "Line {line_no}: '{}' must be a number in range, not '{}'.",
+            CSV_COLUMNS[column],
+            row[column].trim()
+        )
+    })
+}
+
+fn check_version(version: u32) -> Result<(), String> {
+    if version == 0 || version > VERSION {
+        Err(format!(
+            "Game log version {version} isn't supported; this app reads up to version {VERSION}."
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Splits `text` into records, with the line each starts on. Quoted fields
+/// may hold commas, quotes and line breaks; blank lines are skipped.
+fn csv_records(text: &str) -> Result<Vec<(usize, Vec<String>)>, String> {
+    let blank = |fields: &[String]| fields.len() == 1 && fields[0].trim().is_empty();
+    let mut records = Vec::new();
+    let mut fields = vec![String::new()];
+    let mut quoted = false;
+    let (mut line_no, mut start) = (1, 1);
+    let mut chars = text.chars().peekable();
+    while let Some(ch) = chars.next() {
+        let field = fields.last_mut().expect("always one field");
+        match (ch, quoted) {
+            ('"', true) if chars.peek() == Some(&'"') => {
+                chars.next();
+                field.push('"');
+            }
+            ('"', _) => quoted = !quoted,
+            (',', false) => fields.push(String::new()),
+            ('\r', false) if chars.peek() == Some(&'\n') => {}
+            ('\n', false) => {
+                line_no += 1;
+                let record = std::mem::replace(&mut fields, vec![String::new()]);
+                if !blank(&record) {
+                    records.push((start, record));
+                }
+                start = line_no;
+            }
+            _ => {
+                if ch == '\n' {
+                    line_no += 1;
+                }
+                field.push(ch);
+            }
+        }
+    }
+    if quoted {
+        return Err(format!(
+            "Unclosed quote in the game log row starting on line {start}."
+        ));
+    }
+    if !blank(&fields) {
+        records.push((start, fields));
+    }
+    Ok(records)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::planner::SlotLayout;
+    use crate::scoring::SCRABBLE;
+    use crate::session::GameSession;
+
+    fn tiles(letters: &str) -> Vec<String> {
+        letters.chars().map(String::from).collect()
+    }
+
+    fn game(player: &str) -> GameRecord {
+        let mut layouts = vec![SlotLayout::default(); 5];
+        layouts[0] = SlotLayout {
+            bonuses: vec![Bonus::TripleLetter],
+            slot_length: Some(3),
+        };
+        let mut session = GameSession::start(player, &SCRABBLE, layouts);
+        session.draw(tiles("QVT")).unwrap();
+        session.draw(tiles("QAT")).unwrap();
+        session.play(0, "QAT", None).unwrap();
+        session.skip(1).unwrap();
+        session.advance(None).unwrap();
+        session.draw(tiles("JAM")).unwrap();
+        session.play(0, "JAM", None).unwrap();
+        session.finish().unwrap();
+        GameRecord::from_session(&session, 7, 700)
+    }
+
+    #[test]
+    fn json_and_csv_round_trips_keep_every_score() {
+        let record = game("Ada, \"the\" Countess");
+
+        let via_json = from_json(&to_json(&[&record]).unwrap()).unwrap();
+        let via_csv = from_csv(&to_csv(&[&record])).unwrap();
+        for back in [&via_json[0], &via_csv[0]] {
+            assert_eq!(back.player, record.player);
+            assert_eq!(back.total, record.total);
+            assert_eq!(back.rounds.len(), record.rounds.len());
+            for (back, slot) in back.slots.iter().zip(&record.slots) {
+                assert_eq!(back.score, slot.score);
+                assert_eq!(back.racks, slot.racks);
+                assert_eq!(back.skipped, slot.skipped);
+            }
+        }
+
+        let tampered = to_csv(&[&record]).replacen(",QAT,32,", ",QAT,33,", 1);
+        assert!(from_csv(&tampered).unwrap_err().contains("scores 32"));
+        let newer = to_json(&[&record])
+            .unwrap()
+            .replace("\"version\": 1", "\"version\": 2");
+        assert!(from_json(&newer).is_err());
+    }
+
+    #[test]
+    fn csv_rows_are_read_whole_and_checked() {
+        let record = game("Ada\nLovelace");
+        let csv = to_csv(&[&record]);
+        let back = from_csv(&csv.replace('\n', "\r\n")).unwrap();
+        assert_eq!(back[0].player, "Ada\r\nLovelace");
+        assert_eq!(from_csv(&csv).unwrap()[0].player, record.player);
+
+        let header = CSV_COLUMNS.join(",");
+        let overflow = format!("{header}\n1,7,Ada,700,scrabble,0,300,0,,,,,,,true\n");
+        assert!(from_csv(&overflow).unwrap_err().contains("Line 2: 'round'"));
+        let split = format!(
+            "{header}\n1,7,\"Ada\nL\",700,scrabble,0,1,0,,,,,,,true\n1,7,\"Ada\nL\",700,scrabble,5,1,1,,,,,,,true\n"
+        );
+        assert!(from_csv(&split)
+            .unwrap_err()
+            .contains("Line 4: 'total' differs"));
+    }
+}
@@ -0,0 +1,513 @@
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::game::ROUNDS;
+use crate::session::{GameSession, SlotStatus};
+use crate::storage;
+
+/// Games the rolling average in score trends spans.
+const TREND_WINDOW: usize = 5;
+
+/// One slot of a finished game.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SlotRecord {
+    pub round: u8,
+    pub slot_index: u8,
+    pub bonuses: Vec<String>,
+    pub slot_length: Option<u8>,
+    /// The fresh rack, then the rack after each reroll.
+    pub racks: Vec<Vec<String>>,
+    pub word: Option<String>,
+    pub score: Option<u32>,
+    pub skipped: bool,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RoundRecord {
+    pub round: u8,
+    pub multiplier: u32,
+    pub words_total: u32,
+    pub completion_bonus: u32,
+    pub total: u32,
+}
+
+/// A finished game, one line of the history log.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GameRecord {
+    pub id: u64,
+    pub player: String,
+    /// Seconds since the Unix epoch.
+    pub finished_at: u64,
+    pub scoring_profile: String,
+    /// Every round the game reached.
+    pub rounds: Vec<RoundRecord>,
+    pub slots: Vec<SlotRecord>,
+    pub total: u32,
+}
+
+impl GameRecord {
+    pub fn from_session(session: &GameSession, id: u64, finished_at: u64) -> Self {
+        let card = session.card();
+        let reached = 1..=session.round();
+        let slots = reached
+            .clone()
+            .flat_map(|round| {
+                session
+                    .layouts(round)
+                    .iter()
+                    .enumerate()
+                    .map(move |(slot, layout)| {
+                        let played = card.slot(round, slot);
+                        SlotRecord {
+                            round,
+                            slot_index: slot as u8,
+                            bonuses: layout
+                                .bonuses
+                                .iter()
+                                .map(|bonus| bonus.as_code().to_string())
+                                .collect(),
+                            slot_length: layout.slot_length.map(|len| len as u8),
+                            racks: session.draws(round, slot).to_vec(),
+                            word: played.map(|played| played.word.clone()),
+                            score: played.map(|played| played.score),
+                            skipped: session.status(round, slot) == SlotStatus::Skipped,
+                        }
+                    })
+            })
+            .collect();
+        let rounds = reached
+            .map(|round| {
+                let score = card.round_score(round);
+                RoundRecord {
+                    round,
+                    multiplier: score.multiplier,
+                    words_total: score.words_total,
+                    completion_bonus: score.completion_bonus,
+                    total: score.total,
+                }
+            })
+            .collect();
+        Self {
+            id,
+            player: session.player().to_string(),
+            finished_at,
+            scoring_profile: card.profile().name.to_string(),
+            rounds,
+            slots,
+            total: card.total(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RoundStats {
+    pub round: u8,
+    /// Games that reached this round.
+    pub games: u32,
+    pub average_total: f64,
+    /// Share of those games that filled all five slots.
+    pub completion_rate: f64,
+}
+
+#[derive(Debug, Clone)]
+pub struct BestWord {
+    pub game_id: u64,
+    pub round: u8,
+    pub slot_index: u8,
+    pub word: String,
+    pub score: u32,
+}
+
+#[derive(Debug, Clone)]
+pub struct TrendPoint {
+    pub game_id: u64,
+    pub finished_at: u64,
+    pub total: u32,
+    /// Mean total over this game and up to `TREND_WINDOW - 1` before it.
+    pub rolling_average: f64,
+}
+
+#[derive(Debug, Clone)]
+pub struct GameStats {
+    pub games: u32,
+    pub average_total: f64,
+    pub best_total: u32,
+    /// Share of all rounds played that earned the completion bonus.
+    pub completion_rate: f64,
+    pub rounds: Vec<RoundStats>,
+    pub best_words: Vec<BestWord>,
+    pub trend: Vec<TrendPoint>,
+}
+
+/// Averages, bonus rates, top words and the score trend over `games`, which
+/// are expected oldest first.
+pub fn stats(games: &[&GameRecord], best_word_limit: usize) -> GameStats {
+    let count = games.len() as u32;
+    let mean = |sum: f64, count: u32| {
+        if count == 0 {
+            0.0
+        } else {
+            sum / f64::from(count)
+        }
+    };
+
+    let rounds: Vec<RoundStats> = (1..=ROUNDS as u8)
+        .map(|round| {
+            let played: Vec<&RoundRecord> = games
+                .iter()
+                .filter_map(|game| game.rounds.iter().find(|record| record.round == round))
+                .collect();
+            let reached = played.len() as u32;
+            let completed = played
+                .iter()
+                .filter(|record| record.completion_bonus > 0)
+                .count();
+            RoundStats {
+                round,
+                games: reached,
+                average_total: mean(
+                    played.iter().map(|record| f64::from(record.total)).sum(),
+                    reached,
+                ),
+                completion_rate: mean(completed as f64, reached),
+            }
+        })
+        .collect();
+    let rounds_played: u32 = rounds.iter().map(|round| round.games).sum();
+    let rounds_completed: f64 = rounds
+        .iter()
+        .map(|round| round.completion_rate * f64::from(round.games))
+        .sum();
+
+    let mut best_words: Vec<BestWord> = games
+        .iter()
+        .flat_map(|game| {
+            game.slots.iter().filter_map(|slot| {
+                Some(BestWord {
+                    game_id: game.id,
+                    round: slot.round,
+                    slot_index: slot.slot_index,
+                    word: slot.word.clone()?,
+                    score: slot.score?,
+                })
+            })
+        })
+        .collect();
+    best_words.sort_by(|a, b| b.score.cmp(&a.score).then_with(|| a.word.cmp(&b.word)));
+    best_words.truncate(best_word_limit);
+
+    let trend = games
+        .iter()
+        .enumerate()
+        .map(|(idx, game)| {
+            let window = &games[(idx + 1).saturating_sub(TREND_WINDOW)..=idx];
+            TrendPoint {
+                game_id: game.id,
+                finished_at: game.finished_at,
+                total: game.total,
+                rolling_average: mean(
+                    window.iter().map(|game| f64::from(game.total)).sum(),
+                    window.len() as u32,
+                ),
+            }
+        })
+        .collect();
+
+    GameStats {
+        games: count,
+        average_total: mean(games.iter().map(|game| f64::from(game.total)).sum(), count),
+        best_total: games.iter().map(|game| game.total).max().unwrap_or(0),
+        completion_rate: mean(rounds_completed, rounds_played),
+        rounds,
+        best_words,
+        trend,
+    }
+}
+
+/// Finished games, appended one JSON object per line to a log in the app
+/// data dir.
+pub struct GameHistory {
+    path: PathBuf,
+    games: Vec<GameRecord>,
+    recovered: Option<String>,
+    damaged: Option<String>,
+    /// Set when the log couldn't be read at all, so new games aren't written
+    /// after records whose ids are unknown.
+    read_only: bool,
+}
+
+impl GameHistory {
+    /// Reads the log. It never fails, so a bad log can't stop the app from
+    /// starting. A last line that doesn't parse is a write cut short by a
+    /// crash: it is moved to a `.torn` file beside the log and described by
+    /// `recovered`. Bad lines anywhere else are skipped but left in the file,
+    /// and a log that can't be read at all opens empty and read-only; both
+    /// are described by `damaged`.
+    pub fn open(path: PathBuf) -> Self {
+        let text = match storage::read_optional(&path) {
+            Ok(text) => text.unwrap_or_default(),
+            Err(err) => {
+                return Self {
+                    path,
+                    games: Vec::new(),
+                    recovered: None,
+                    damaged: Some(format!(
+                        "{err}; finished games won't be saved until it can be read."
+                    )),
+                    read_only: true,
+                }
+            }
+        };
+        let lines: Vec<&str> = text
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .collect();
+        let mut games = Vec::with_capacity(lines.len());
+        let mut recovered = None;
+        let mut bad_lines = Vec::new();
+        for (idx, line) in lines.iter().enumerate() {
+            match serde_json::from_str(line) {
+                Ok(game) => games.push(game),
+                Err(err) if idx + 1 == lines.len() => match set_aside(&path, &lines[..idx], line) {
+                    Ok(torn) => {
+                        recovered = Some(format!(
+                            "The last game in {} couldn't be read ({err}) and was moved to {}.",
+                            path.display(),
+                            torn.display()
+                        ))
+                    }
+                    Err(_) => bad_lines.push((idx, err)),
+                },
+                Err(err) => bad_lines.push((idx, err)),
+            }
+        }
+        // Imports are appended after newer games, so the log isn't in order.
+        games.sort_by_key(|game: &GameRecord| game.finished_at);
+        let damaged = bad_lines.first().map(|(idx, err)| {
+            format!(
+                "{} line(s) of {} couldn't be read and were skipped, starting at line {} ({err}); they are left in the file.",
+                bad_lines.len(),
+                path.display(),
+                idx + 1
+            )
+        });
+        Self {
+            path,
+            games,
+            recovered,
+            damaged,
+            read_only: false,
+        }
+    }
+
+    /// What `open` did with an unreadable last line, if it found one.
+    pub fn recovered(&self) -> Option<&str> {
+        self.recovered.as_deref()
+    }
+
+    /// Lines `open` skipped, or why the log couldn't be read at all.
+    pub fn damaged(&self) -> Option<&str> {
+        self.damaged.as_deref()
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Games for one player, matched without regard to case, or every game,
+    /// oldest first.
+    pub fn for_player(&self, player: Option<&str>) -> Vec<&GameRecord> {
+        let player = player.map(str::trim).filter(|name| !name.is_empty());
+        self.games
+            .iter()
+            .filter(|game| player.is_none_or(|name| game.player.eq_ignore_ascii_case(name)))
+            .collect()
+    }
+
+    pub fn find(&self, id: u64) -> Option<&GameRecord> {
+        self.games.iter().find(|game| game.id == id)
+    }
+
+    pub fn players(&self) -> Vec<String> {
+        self.games
+            .iter()
+            .map(|game| game.player.clone())
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect()
+    }
+
+    pub fn next_id(&self) -> u64 {
+        self.games.iter().map(|game| game.id).max().unwrap_or(0) + 1
+    }
+
+    /// Finishes `session` and logs it. The session only changes once the
+    /// game is written, so a failed write leaves it open to retry.
+    pub fn finish(&mut self, session: &mut GameSession, finished_at: u64) -> Result<(), String> {
+        let mut finished = session.clone();
+        finished.finish()?;
+        self.append(GameRecord::from_session(
+            &finished,
+            self.next_id(),
+            finished_at,
+        ))?;
+        *session = finished;
+        Ok(())
+    }
+
+    pub fn append(&mut self, record: GameRecord) -> Result<(), String> {
+        self.append_all(vec![record])
+    }
+
+    /// Logs `records` in one write, so either all of them are kept or none,
+    /// and files each by `finished_at` among the games already loaded.
+    pub fn append_all(&mut self, records: Vec<GameRecord>) -> Result<(), String> {
+        if self.read_only {
+            return Err(self.damaged.clone().unwrap_or_default());
+        }
+        let lines = records
+            .iter()
+            .map(serde_json::to_string)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|err| format!("Failed to encode game history: {err}"))?;
+        storage::append_lines(&self.path, &lines)?;
+        for record in records {
+            let at = self
+                .games
+                .partition_point(|game| game.finished_at <= record.finished_at);
+            self.games.insert(at, record);
+        }
+        Ok(())
+    }
+}
+
+/// Moves a torn last line to a `.torn` file and rewrites the log with the
+/// lines before it.
+fn set_aside(path: &Path, kept: &[&str], torn_line: &str) -> Result<PathBuf, String> {
+    let torn = path.with_extension("jsonl.torn");
+    storage::append_line(&torn, torn_line)?;
+    let kept: String = kept.iter().map(|line| format!("{line}\n")).collect();
+    storage::write_atomic(path, &kept)?;
+    Ok(torn)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+    use crate::game::SLOTS_PER_ROUND;
+    use crate::planner::SlotLayout;
+    use crate::scoring::SCRABBLE;
+
+    fn game(id: u64, words: &[&str], rounds: u8) -> GameRecord {
+        let mut session = GameSession::start("Ada", &SCRABBLE, vec![SlotLayout::default(); 5]);
+        for round in 1..=rounds {
+            if round > 1 {
+                session.advance(None).unwrap();
+            }
+            for (slot, word) in words.iter().enumerate() {
+                session.draw(vec![word.to_string()]).unwrap();
+                session.play(slot, word, None).unwrap();
+            }
+        }
+        session.finish().unwrap();
+        GameRecord::from_session(&session, id, id * 100)
+    }
+
+    #[test]
+    fn stats_roll_up_rounds_words_and_trend() {
+        let full = ["AT"; SLOTS_PER_ROUND];
+        // Round 1: 5 × AT(2) + 100; round 2: ×2 words, +100.
+        let first = game(1, &full, 2);
+        assert_eq!(first.total, 110 + 120);
+        assert_eq!(first.slots[0].racks, vec![vec!["AT".to_string()]]);
+        let second = game(2, &["QAT", "AT"], 1);
+        assert_eq!(second.total, 12 + 2);
+
+        let line = serde_json::to_string(&second).unwrap();
+        let back: GameRecord = serde_json::from_str(&line).unwrap();
+        assert_eq!(back.slots.len(), second.slots.len());
+
+        let stats = stats(&[&first, &second], 3);
+        assert_eq!(stats.games, 2);
+        assert_eq!(stats.best_total, 230);
+        assert_eq!(stats.rounds[0].games, 2);
+        assert_eq!(stats.rounds[0].completion_rate, 0.5);
+        assert_eq!(stats.rounds[1].games, 1);
+        // Two of the three rounds played were completed.
+        assert!((stats.completion_rate - 2.0 / 3.0).abs() < 1e-12);
+        assert_eq!(stats.best_words[0].word, "QAT");
+        assert_eq!(stats.trend[1].rolling_average, 122.0);
+    }
+
+    #[test]
+    fn finished_games_are_logged_once() {
+        let dir = std::env::temp_dir().join(format!("yatzy-history-{}", std::process::id()));
+        let mut session = GameSession::start("Ada", &SCRABBLE, vec![SlotLayout::default(); 5]);
+        session.play(0, "QAT", None).unwrap();
+
+        // The log's directory is a file, so the write fails.
+        let blocked = dir.join("blocked");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(&blocked, "").unwrap();
+        let mut broken = GameHistory::open(blocked.join("games.jsonl"));
+        assert!(broken.finish(&mut session, 100).is_err());
+        assert!(!session.is_finished());
+        assert!(session.can_undo() && !session.can_redo());
+
+        let mut log = GameHistory::open(dir.join("games.jsonl"));
+        log.finish(&mut session, 100).unwrap();
+        assert!(session.is_finished());
+        assert!(session.undo().is_err() && session.redo().is_err());
+        assert!(log.finish(&mut session, 200).is_err());
+
+        // Imported games are filed by when they finished, in memory and on
+        // the next open, though the log keeps them in the order written.
+        let mut older = game(2, &["AT"], 1);
+        older.finished_at = 50;
+        log.append_all(vec![game(3, &["AT"], 1), older]).unwrap();
+        let ids = |log: &GameHistory| -> Vec<u64> {
+            log.for_player(None).iter().map(|game| game.id).collect()
+        };
+        assert_eq!(ids(&log), [2, 1, 3]);
+        let reopened = GameHistory::open(dir.join("games.jsonl"));
+        fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(ids(&reopened), [2, 1, 3]);
+        assert_eq!(reopened.find(1).unwrap().total, 12);
+    }
+
+    #[test]
+    fn torn_last_line_is_set_aside() {
+        let dir = std::env::temp_dir().join(format!("yatzy-torn-{}", std::process::id()));
+        let path = dir.join("games.jsonl");
+        fs::create_dir_all(&dir).unwrap();
+        let line = serde_json::to_string(&game(1, &["AT"], 1)).unwrap();
+        fs::write(&path, format!("{line}\n{{\"id\":2,\"play")).unwrap();
+
+        let mut log = GameHistory::open(path.clone());
+        assert_eq!(log.for_player(None).len(), 1);
+        assert!(log.recovered().unwrap().contains("games.jsonl.torn"));
+        log.append(game(2, &["QAT"], 1)).unwrap();
+        let reopened = GameHistory::open(path.clone());
+        let torn = fs::read_to_string(dir.join("games.jsonl.torn")).unwrap();
+
+        // A bad line before the last one is not a torn write: it is skipped
+        // and reported, and stays in the file.
+        fs::write(&path, format!("oops\n{line}\n")).unwrap();
+        let corrupt = GameHistory::open(path.clone());
+        let left = fs::read_to_string(&path).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(reopened.for_player(None).len(), 2);
+        assert!(reopened.recovered().is_none() && reopened.damaged().is_none());
+        assert_eq!(torn, "{\"id\":2,\"play\n");
+        assert_eq!(corrupt.for_player(None).len(), 1);
+        assert!(corrupt.damaged().unwrap().contains("starting at line 1"));
+        assert!(left.starts_with("oops\n"));
+    }
+}
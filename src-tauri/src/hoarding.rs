@@ -0,0 +1,172 @@
+use crate::game::{ROUNDS, SLOTS_PER_ROUND};
+use crate::scoring::{Bonus, ScoringProfile};
+
+/// Letters worth at least this much are the power letters `YatzyRules.md`
+/// suggests holding for a premium square.
+pub const POWER_LETTER_MIN_VALUE: u8 = 5;
+/// Chance one later slot has a TL a held power letter can be parked on.
+const TL_PARK_ODDS: f64 = 0.5;
+/// Chance a power letter left unplayed is still on the rack when that slot
+/// comes up, after the rerolls in between.
+const HELD_ODDS: f64 = 0.5;
+/// Chance a held letter also survives into the next round's rack; applied
+/// once per round boundary, so far-off rounds count for little.
+const ROUND_CARRY_ODDS: f64 = 0.5;
+
+/// What power letters on the rack are worth if held for a later slot, this
+/// round or a later one at its higher multiplier, instead of being spent
+/// now. Only the best of those chances counts, since a letter is played once.
+#[derive(Debug, Clone)]
+pub struct Hoard {
+    /// Open slots in this round after the one being played.
+    pub slots_left: usize,
+    pub round: u8,
+    /// Round where a held letter is expected to earn the most.
+    pub payoff_round: u8,
+    /// Points a letter is expected to earn later over the average tile that
+    /// would otherwise fill its place.
+    gains: [f64; 26],
+}
+
+impl Hoard {
+    /// `None` in the last open slot of the last round, when nothing is left
+    /// to save letters for.
+    pub fn new(
+        profile: &ScoringProfile,
+        round: u8,
+        slots_left: usize,
+        bag: &[u8; 26],
+    ) -> Option<Self> {
+        let triple = f64::from(profile.letter_multiplier(Bonus::TripleLetter));
+        let letter_multiplier = |slots: usize| {
+            let parked = 1.0 - (1.0 - TL_PARK_ODDS).powi(slots as i32);
+            parked * triple + (1.0 - parked)
+        };
+        let this_round = (slots_left > 0).then(|| {
+            let odds = HELD_ODDS * letter_multiplier(slots_left);
+            (round, odds * f64::from(profile.round_multiplier(round)))
+        });
+        let later_rounds = (round + 1..=ROUNDS as u8).map(|later| {
+            let odds = HELD_ODDS
+                * ROUND_CARRY_ODDS.powi(i32::from(later - round))
+                * letter_multiplier(SLOTS_PER_ROUND);
+            (later, odds * f64::from(profile.round_multiplier(later)))
+        });
+        let (payoff_round, later_multiplier) = this_round
+            .into_iter()
+            .chain(later_rounds)
+            .max_by(|a, b| a.1.total_cmp(&b.1))?;
+
+        let mean = profile.mean_letter_value(bag);
+        let gains = std::array::from_fn(|idx| {
+            let value = profile
+                .letter_value((b'A' + idx as u8) as char)
+                .unwrap_or(0);
+            if value >= POWER_LETTER_MIN_VALUE {
+                (f64::from(value) - mean) * later_multiplier
+            } else {
+                0.0
+            }
+        });
+        Some(Self {
+            slots_left,
+            round,
+            payoff_round,
+            gains,
+        })
+    }
+
+    /// Where held letters pay off, for notes: "later this round" or "in round N".
+    pub fn payoff(&self) -> String {
+        if self.payoff_round == self.round {
+            "later this round".to_string()
+        } else {
+            format!("in round {}", self.payoff_round)
+        }
+    }
+
+    pub fn letter_gain(&self, ch: char) -> f64 {
+        if ch.is_ascii_uppercase() {
+            self.gains[(ch as u8 - b'A') as usize]
+        } else {
+            0.0
+        }
+    }
+
+    /// Later value spent by playing these tiles now; blanks spend nothing.
+    pub fn cost<'a>(&self, tiles: impl IntoIterator<Item = (&'a str, bool)>) -> f64 {
+        tiles
+            .into_iter()
+            .filter(|(_, blank)| !blank)
+            .flat_map(|(text, _)| text.chars())
+            .map(|ch| self.letter_gain(ch))
+            .sum()
+    }
+
+    /// Power letters among these tiles, blanks aside.
+    pub fn power_letters<'a>(&self, tiles: impl IntoIterator<Item = (&'a str, bool)>) -> Vec<char> {
+        tiles
+            .into_iter()
+            .filter(|(_, blank)| !blank)
+            .flat_map(|(text, _)| text.chars())
+            .filter(|&ch| self.letter_gain(ch) > 0.0)
+            .collect()
+    }
+
+    /// Warning for reroll advice that throws back a power letter.
+    pub fn reroll_note(&self, reroll: &[char]) -> Option<String> {
+        let letters: String = reroll
+            .iter()
+            .filter(|&&ch| self.letter_gain(ch) > 0.0)
+            .collect();
+        if letters.is_empty() {
+            return None;
+        }
+        let value: f64 = letters.chars().map(|ch| self.letter_gain(ch)).sum();
+        Some(format!(
+            "Rerolling {letters} gives up about {value:.0} points it could earn on a TL {}",
+            self.payoff()
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scoring::SCRABBLE;
+    use crate::solver::LETTER_BAG_COUNTS;
+
+    #[test]
+    fn power_letters_are_held_for_the_best_later_slot() {
+        assert!(Hoard::new(&SCRABBLE, 5, 0, &LETTER_BAG_COUNTS).is_none());
+        let edge = 10.0 - SCRABBLE.mean_letter_value(&LETTER_BAG_COUNTS);
+
+        // Last round: Z(10) less a ~1.9 point average tile, ×2 expected
+        // letter bonus with one slot left, held half the time, ×5.
+        let late = Hoard::new(&SCRABBLE, 5, 1, &LETTER_BAG_COUNTS).unwrap();
+        assert_eq!(late.payoff(), "later this round");
+        assert_eq!(late.letter_gain('E'), 0.0);
+        assert!((late.letter_gain('Z') - 5.0 * edge).abs() < 1e-9);
+        assert!(late.letter_gain('Z') > late.letter_gain('K') && late.letter_gain('K') > 0.0);
+
+        // Round 4's last slot: only round 5 is left, and the letter has to
+        // survive into it.
+        let closing = Hoard::new(&SCRABBLE, 4, 0, &LETTER_BAG_COUNTS).unwrap();
+        assert_eq!(closing.payoff(), "in round 5");
+        let full_round = 1.0 + 2.0 * (1.0 - 0.5f64.powi(5));
+        assert!((closing.letter_gain('Z') - 0.25 * full_round * 5.0 * edge).abs() < 1e-9);
+
+        // Early on, later rounds' multipliers are discounted round by round,
+        // so the gain stays well under what the letter earns in round 5.
+        let early = Hoard::new(&SCRABBLE, 1, 4, &LETTER_BAG_COUNTS).unwrap();
+        assert!(early.letter_gain('Z') < late.letter_gain('Z') / 2.0);
+
+        assert_eq!(late.cost([("Z", true), ("A", false)]), 0.0);
+        assert_eq!(
+            late.power_letters([("Z", false), ("E", false), ("K", true)]),
+            ['Z']
+        );
+        assert!(late.reroll_note(&['E', 'A']).is_none());
+        assert!(closing.reroll_note(&['Z']).unwrap().contains("in round 5"));
+    }
+}
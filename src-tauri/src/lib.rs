@@ -1,136 +1,1533 @@
 #![recursion_limit = "256"]
 
+mod acceptance;
+mod anagram;
+mod calibration;
+mod dictionary;
+mod explain;
+mod game;
+mod game_log;
+mod history;
+mod hoarding;
 mod models;
+mod planner;
+mod reroll_ev;
+mod review;
 mod scoring;
+mod session;
+mod simulator;
 mod solver;
+mod storage;
+mod targets;
+mod tiles;
+mod word_filter;
+mod word_store;
 
 use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard};
 
-use models::{RerollSuggestion, SolveRackRequest, SolveRackResponse, WordRecommendation};
-use tauri::Manager;
+use acceptance::AcceptanceLog;
+use calibration::{DrawLog, LetterModel};
+use dictionary::{Dictionary, SourceKind};
+use game::{Placement, Scorecard};
+use history::{GameHistory, GameRecord};
+use models::{
+    BestWordReport, BlankAssignment, DictionaryReloadResponse, DictionarySourceReport,
+    ExplainWordRequest, ExplainWordResponse, GameHistoryStatusResponse, GameReviewResponse,
+    GameScoreResponse, GameSessionResponse, GameStatsResponse, LetterCalibrationResponse,
+    LetterEstimateReport, LetterScoreReport, MistakeLossReport, PlanRoundRequest,
+    PlanRoundResponse, RerollSimulationResponse, RerollSuggestion, RoundLossReport,
+    RoundScoreReport, RoundStatsReport, ScoreBreakdownReport, ScoreGameRequest, ScorePercentile,
+    ScoreTrendPoint, SessionSlotReport, SimulateRerollRequest, SlotLayoutRequest, SlotPlanReport,
+    SlotReviewReport, SlotScoreReport, SolveRackRequest, SolveRackResponse, StartGameRequest,
+    WordConfidence, WordExclusion, WordListResponse, WordRecommendation,
+};
+use scoring::{Bonus, ScoreContext, ScoringProfile};
+use session::{GameSession, SlotStatus};
+use tauri::{AppHandle, Emitter, Manager, State};
+use word_store::WordStore;
 
 const DEFAULT_LIMIT: usize = 40;
 const REROLL_SUGGESTION_LIMIT: usize = 6;
+const TARGET_WORD_LIMIT: usize = 3;
+/// Rerolls assumed left when a request doesn't say: the two passes of
+/// `YatzyRerollStrategy.md`.
+const DEFAULT_REROLLS: u8 = 2;
+const MAX_REROLLS: u8 = 3;
+const DICTIONARY_DIR: &str = "dictionary";
+/// Emitted with the error message when the startup dictionary load fails.
+const DICTIONARY_LOAD_FAILED_EVENT: &str = "dictionary-load-failed";
+const BLOCKLIST_FILE: &str = "blocked-words.txt";
+const ALLOWLIST_FILE: &str = "allowed-words.txt";
+const WORD_OUTCOMES_FILE: &str = "word-outcomes.json";
+const LETTER_DRAWS_FILE: &str = "letter-draws.json";
+const GAME_HISTORY_FILE: &str = "game-history.jsonl";
+const BEST_WORD_LIMIT: usize = 10;
 
-#[tauri::command]
-fn solve_rack_command(request: SolveRackRequest) -> Result<SolveRackResponse, String> {
+/// Words the game has rejected, kept in the app data dir so they survive
+/// reinstalls and can be exported for the rest of the team.
+struct Blocklist(Mutex<WordStore>);
+
+/// Words the game accepts even though the bundled word lists filter them out.
+struct Allowlist(Mutex<WordStore>);
+
+/// Accept/reject history for words the user has played in game.
+struct WordOutcomes(Mutex<AcceptanceLog>);
+
+/// Letters seen in real racks and rerolls, for calibrating draw odds.
+struct LetterDraws(Mutex<DrawLog>);
+
+/// The match being played, if any.
+struct ActiveGame(Mutex<Option<GameSession>>);
+
+/// Finished games, for statistics and post-game review.
+struct GameLog(Mutex<GameHistory>);
+
+/// Rack, bonuses and filters from a `SolveRackRequest` after validation.
+struct RackInputs {
+    tiles: Vec<String>,
+    target_word_length: Option<u8>,
+    invalid: HashSet<String>,
+    bonuses: Vec<Bonus>,
+    round: u8,
+    min_confidence: Option<f64>,
+    profile: &'static ScoringProfile,
+    slot_length: Option<u8>,
+    letter_model: LetterModel,
+    rerolls_remaining: u8,
+    max_reroll_tiles: usize,
+    plan_rerolls: bool,
+    hold_power_letters: bool,
+}
+
+impl RackInputs {
+    fn target_length(&self) -> Option<usize> {
+        self.target_word_length.map(usize::from)
+    }
+
+    fn score_context(&self) -> ScoreContext<'_> {
+        ScoreContext {
+            profile: self.profile,
+            bonuses: &self.bonuses,
+            round: self.round,
+            slot_length: self.slot_length.map(usize::from),
+        }
+    }
+}
+
+fn normalize_request(request: SolveRackRequest) -> Result<RackInputs, String> {
     let SolveRackRequest {
         rack_letters,
         target_word_length,
         invalid_words,
         rack_bonuses,
         round,
+        min_confidence,
+        scoring_profile,
+        slot_length,
+        letter_model,
+        rerolls_remaining,
+        max_reroll_tiles,
+        plan_rerolls,
+        hold_power_letters,
+        slot_index: _,
     } = request;
 
-    let normalized_letters: Vec<char> = rack_letters
-        .into_iter()
-        .flat_map(|entry| entry.chars().next())
-        .filter(|ch| ch.is_ascii_alphabetic())
-        .map(|ch| ch.to_ascii_uppercase())
+    let tiles: Vec<String> = rack_letters
+        .iter()
+        .filter_map(|entry| tiles::parse_tile(entry))
         .collect();
-
-    if normalized_letters.is_empty() {
-        return Err("Add at least one rack letter before solving.".into());
-    }
+    tiles::check_rack_size(&tiles)?;
 
     if let Some(len) = target_word_length {
-        if len < 2 || len > 15 {
+        if !(2..=15).contains(&len) {
             return Err("Target word length must be between 2 and 15.".into());
         }
     }
 
-    let normalized_invalid: HashSet<String> = invalid_words
+    let invalid: HashSet<String> = invalid_words
         .into_iter()
         .map(|word| word.trim().to_ascii_uppercase())
         .filter(|word| !word.is_empty())
         .collect();
 
-    let target_filter = target_word_length.map(|len| len as usize);
-
-    let round_value = round.unwrap_or(1);
-    if !(1..=5).contains(&round_value) {
+    let round = round.unwrap_or(1);
+    if !(1..=5).contains(&round) {
         return Err("Round must be between 1 and 5.".into());
     }
-    let round_multiplier = u32::from(round_value);
 
-    let normalized_bonuses: Vec<solver::Bonus> = rack_bonuses
+    let bonuses: Vec<Bonus> = rack_bonuses
         .into_iter()
-        .map(|value| solver::Bonus::from_str_raw(&value))
+        .map(|value| Bonus::from_str_raw(&value))
         .collect();
 
-    let candidates = solver::solve_rack(
-        &normalized_letters,
-        target_filter,
-        &normalized_invalid,
-        DEFAULT_LIMIT,
-        &normalized_bonuses,
-        round_multiplier,
-    );
+    if min_confidence.is_some_and(|value| !(0.0..=1.0).contains(&value)) {
+        return Err("Minimum confidence must be between 0 and 1.".into());
+    }
 
-    let recommendations: Vec<WordRecommendation> = candidates
-        .into_iter()
-        .map(|candidate| WordRecommendation {
-            slot_index: None,
-            word: candidate.word.clone(),
-            score: Some(candidate.score as f64),
-            computed_score: Some(candidate.score as f64),
-            confidence: None,
-            letters_used: candidate.word.chars().map(|ch| ch.to_string()).collect(),
-            placement_notes: None,
-        })
-        .collect();
+    let profile = resolve_profile(scoring_profile.as_deref())?;
 
-    let reroll_target = target_filter.unwrap_or(normalized_letters.len());
-    let best_word = recommendations.first().map(|rec| rec.word.as_str());
-    let reroll_suggestions: Vec<RerollSuggestion> = solver::suggest_rerolls(
-        &normalized_letters,
-        reroll_target,
-        &normalized_invalid,
-        REROLL_SUGGESTION_LIMIT,
-        best_word,
-    )
-    .into_iter()
-    .map(|advice| RerollSuggestion {
-        target_word: advice.target_word,
-        missing_letters: advice
-            .missing_letters
-            .into_iter()
-            .map(|ch| ch.to_string())
+    if let Some(len) = slot_length {
+        if !(2..=15).contains(&len) {
+            return Err("Slot length must be between 2 and 15.".into());
+        }
+        if bonuses.len() > usize::from(len) {
+            return Err(format!(
+                "The slot has {len} squares but {} bonuses were given.",
+                bonuses.len()
+            ));
+        }
+        if target_word_length.is_some_and(|target| target > len) {
+            return Err("Target word length can't be longer than the slot.".into());
+        }
+    }
+
+    let letter_model = match letter_model.as_deref().map(str::trim) {
+        Some(name) if !name.is_empty() => {
+            LetterModel::by_name(name).ok_or_else(|| format!("Unknown letter model '{name}'."))?
+        }
+        _ => LetterModel::default(),
+    };
+
+    let rerolls_remaining = rerolls_remaining.unwrap_or(DEFAULT_REROLLS);
+    if rerolls_remaining > MAX_REROLLS {
+        return Err(format!(
+            "Rerolls remaining must be between 0 and {MAX_REROLLS}."
+        ));
+    }
+
+    let max_reroll_tiles = match max_reroll_tiles {
+        Some(0) => return Err("Max reroll tiles must be at least 1.".into()),
+        Some(count) => usize::from(count),
+        None => reroll_ev::MAX_EXACT_REROLLS,
+    }
+    .min(reroll_ev::MAX_REROLL_TILES)
+    .min(tiles.len().max(1));
+
+    Ok(RackInputs {
+        tiles,
+        target_word_length,
+        invalid,
+        bonuses,
+        round,
+        min_confidence,
+        profile,
+        slot_length,
+        letter_model,
+        rerolls_remaining,
+        max_reroll_tiles,
+        plan_rerolls,
+        hold_power_letters: hold_power_letters.unwrap_or(true),
+    })
+}
+
+fn recommendation(candidate: solver::RackCandidate) -> WordRecommendation {
+    WordRecommendation {
+        slot_index: None,
+        word: candidate.word.clone(),
+        score: Some(candidate.score as f64),
+        computed_score: Some(candidate.score as f64),
+        confidence: candidate.confidence,
+        letters_used: candidate
+            .breakdown
+            .tiles
+            .iter()
+            .map(|tile| {
+                if tile.blank {
+                    scoring::BLANK.to_string()
+                } else {
+                    tile.tile.clone()
+                }
+            })
             .collect(),
-        reroll_letters: advice
-            .reroll_letters
-            .into_iter()
-            .map(|ch| ch.to_string())
+        blank_letters: candidate
+            .breakdown
+            .tiles
+            .iter()
+            .enumerate()
+            .filter(|(_, tile)| tile.blank)
+            .map(|(position, tile)| BlankAssignment {
+                position: position as u8,
+                letter: tile.tile.clone(),
+            })
             .collect(),
-        keep_letters: advice
-            .keep_letters
-            .into_iter()
-            .map(|ch| ch.to_string())
+        slot_offset: Some(candidate.breakdown.offset as u8),
+        covered_squares: (0..candidate.breakdown.tiles.len())
+            .map(|idx| (candidate.breakdown.offset + idx) as u8)
             .collect(),
-        estimated_score: advice.estimated_score.map(|value| f64::from(value)),
-        success_probability: advice.success_probability,
-        phase: Some(advice.phase.to_string()),
-        notes: advice.notes,
-        focus_tags: advice.focus_tags,
-    })
-    .collect();
+        placement_notes: Some(candidate.breakdown.summary()),
+        score_breakdown: Some(breakdown_report(&candidate.breakdown)),
+        from_allowlist: candidate.from_allowlist,
+    }
+}
+
+fn breakdown_report(breakdown: &solver::ScoreBreakdown) -> ScoreBreakdownReport {
+    ScoreBreakdownReport {
+        letters: breakdown
+            .tiles
+            .iter()
+            .map(|tile| LetterScoreReport {
+                letter: tile.tile.clone(),
+                base: tile.base,
+                bonus: tile.bonus.as_code().to_string(),
+                letter_multiplier: tile.letter_multiplier,
+                value: tile.value,
+                blank: tile.blank,
+            })
+            .collect(),
+        letter_sum: breakdown.letter_sum,
+        word_multiplier: breakdown.word_multiplier,
+        round_multiplier: breakdown.round_multiplier,
+        total: breakdown.total,
+    }
+}
+
+fn resolve_profile(name: Option<&str>) -> Result<&'static ScoringProfile, String> {
+    match name.map(str::trim) {
+        Some(name) if !name.is_empty() => ScoringProfile::by_name(name)
+            .ok_or_else(|| format!("Unknown scoring profile '{name}'.")),
+        _ => Ok(scoring::DEFAULT_PROFILE),
+    }
+}
+
+/// Async so the expected-value pass, which can take seconds with a wide
+/// `max_reroll_tiles`, runs on a blocking thread instead of the main one.
+#[tauri::command]
+async fn solve_rack_command(
+    request: SolveRackRequest,
+    blocklist: State<'_, Blocklist>,
+    outcomes: State<'_, WordOutcomes>,
+    draws: State<'_, LetterDraws>,
+    game: State<'_, ActiveGame>,
+) -> Result<SolveRackResponse, String> {
+    let (request, open_slots) = match lock_game(&game)?.as_ref() {
+        Some(session) => (
+            with_session_defaults(request, session)?,
+            (0..game::SLOTS_PER_ROUND)
+                .filter(|&slot| matches!(session.status(session.round(), slot), SlotStatus::Open))
+                .count(),
+        ),
+        None if request.slot_index.is_some() => {
+            return Err("Start a game before solving for one of its slots.".into())
+        }
+        None => (request, game::SLOTS_PER_ROUND),
+    };
+    let inputs = normalize_request(request)?;
+    if inputs.tiles.is_empty() {
+        return Err("Add at least one rack letter before solving.".into());
+    }
+
+    let target_filter = inputs.target_length();
+    let RackInputs {
+        tiles: normalized_tiles,
+        target_word_length,
+        invalid: mut normalized_invalid,
+        bonuses: normalized_bonuses,
+        round: round_value,
+        min_confidence,
+        profile,
+        slot_length,
+        letter_model,
+        rerolls_remaining,
+        max_reroll_tiles,
+        plan_rerolls,
+        hold_power_letters,
+    } = inputs;
+    let bag = lock_draws(&draws)?.bag(letter_model);
+    // The slot being solved is one of the open ones.
+    let slots_left = open_slots.saturating_sub(1);
+    let hoard = hold_power_letters
+        .then(|| hoarding::Hoard::new(profile, round_value, slots_left, &bag))
+        .flatten();
+    normalized_invalid.extend(lock_store(&blocklist.0)?.words().iter().cloned());
+    let scoring = ScoreContext {
+        profile,
+        bonuses: &normalized_bonuses,
+        round: round_value,
+        slot_length: slot_length.map(usize::from),
+    };
+
+    let candidates = {
+        let acceptance_log = lock_outcomes(&outcomes)?;
+        solver::solve_rack(
+            &normalized_tiles,
+            target_filter,
+            &normalized_invalid,
+            DEFAULT_LIMIT,
+            &scoring,
+            Some(&solver::ConfidenceRanking {
+                log: &acceptance_log,
+                min_confidence,
+            }),
+            hoard.as_ref(),
+        )
+    };
+
+    let notes: Vec<String> = hoard
+        .as_ref()
+        .and_then(|hoard| hoard_note(hoard, &candidates, min_confidence))
+        .into_iter()
+        .collect();
+    let recommendations: Vec<WordRecommendation> =
+        candidates.into_iter().map(recommendation).collect();
 
-    let rack_for_response = normalized_letters
+    let reroll_target = target_filter.unwrap_or(normalized_tiles.len());
+    let best_word = recommendations.first().map(|rec| rec.word.as_str());
+    // The expected-value pass and the target-word search both walk many
+    // racks, so they share one blocking thread and are skipped entirely
+    // when no reroll is left.
+    let (expected_value, target_words) = if rerolls_remaining > 0 {
+        let (tiles, invalid, bonuses) = (
+            normalized_tiles.clone(),
+            normalized_invalid.clone(),
+            normalized_bonuses.clone(),
+        );
+        tauri::async_runtime::spawn_blocking(move || {
+            let scoring = ScoreContext {
+                profile,
+                bonuses: &bonuses,
+                round: round_value,
+                slot_length: slot_length.map(usize::from),
+            };
+            let expected_value = reroll_ev::best_keep_advice(
+                &reroll_ev::RerollInputs {
+                    tiles: &tiles,
+                    target_length: target_filter,
+                    invalid: &invalid,
+                    scoring: &scoring,
+                    bag: &bag,
+                },
+                if plan_rerolls { rerolls_remaining } else { 1 },
+                max_reroll_tiles,
+            );
+            let target_words = targets::suggest_targets(
+                &targets::TargetInputs {
+                    tiles: &tiles,
+                    target_length: reroll_target,
+                    invalid: &invalid,
+                    scoring: &scoring,
+                    bag: &bag,
+                    rerolls: rerolls_remaining,
+                },
+                TARGET_WORD_LIMIT,
+            );
+            (expected_value, target_words)
+        })
+        .await
+        .map_err(|err| format!("Failed to compute reroll advice: {err}"))?
+    } else {
+        (None, Vec::new())
+    };
+    let reroll_suggestions: Vec<RerollSuggestion> = expected_value
         .into_iter()
-        .map(|ch| ch.to_string())
+        .chain(target_words)
+        .chain(solver::suggest_rerolls(
+            &normalized_tiles,
+            reroll_target,
+            &normalized_invalid,
+            REROLL_SUGGESTION_LIMIT,
+            best_word,
+            profile,
+            &bag,
+        ))
+        // With one reroll left there is no second pass to set up for.
+        .filter(|advice| match rerolls_remaining {
+            0 => false,
+            1 => advice.phase != "foundation",
+            _ => true,
+        })
+        .take(REROLL_SUGGESTION_LIMIT)
+        .map(|advice| {
+            let hoard_note = hoard
+                .as_ref()
+                .and_then(|hoard| hoard.reroll_note(&advice.reroll_letters));
+            RerollSuggestion {
+                target_word: advice.target_word,
+                missing_letters: advice
+                    .missing_letters
+                    .into_iter()
+                    .map(|ch| ch.to_string())
+                    .collect(),
+                reroll_letters: advice
+                    .reroll_letters
+                    .into_iter()
+                    .map(|ch| ch.to_string())
+                    .collect(),
+                keep_letters: advice
+                    .keep_letters
+                    .into_iter()
+                    .map(|ch| ch.to_string())
+                    .chain(advice.held_tiles)
+                    .collect(),
+                estimated_score: advice.estimated_score.map(|value| f64::from(value)),
+                success_probability: advice.success_probability,
+                phase: Some(advice.phase.to_string()),
+                notes: advice.notes.into_iter().chain(hoard_note).collect(),
+                focus_tags: advice.focus_tags,
+            }
+        })
         .collect();
+
     let bonuses_for_response = normalized_bonuses
         .iter()
         .map(|bonus| bonus.as_code().to_string())
         .collect();
 
     Ok(SolveRackResponse {
-        rack_letters: rack_for_response,
+        rack_letters: normalized_tiles,
         target_word_length,
         rack_bonuses: bonuses_for_response,
         round: Some(round_value),
+        slot_length,
+        scoring_profile: profile.name.to_string(),
+        letter_model: letter_model.name().to_string(),
         recommendations,
         reroll_suggestions,
+        notes,
+    })
+}
+
+/// Explains the hold-or-spend trade-off behind the top play: either a
+/// richer word was passed over to keep power letters, or the top play spends
+/// them because that beats holding them.
+fn hoard_note(
+    hoard: &hoarding::Hoard,
+    candidates: &[solver::RackCandidate],
+    min_confidence: Option<f64>,
+) -> Option<String> {
+    let play = candidates.first()?;
+    let spent = |candidate: &solver::RackCandidate| {
+        hoard.power_letters(
+            candidate
+                .breakdown
+                .tiles
+                .iter()
+                .map(|tile| (tile.tile.as_str(), tile.blank)),
+        )
+    };
+    let held_for = if hoard.payoff_round == hoard.round {
+        let slots = hoard.slots_left;
+        format!(
+            "the {slots} slot{} left this round",
+            if slots == 1 { "" } else { "s" }
+        )
+    } else {
+        format!("round {}", hoard.payoff_round)
+    };
+
+    let richest = candidates
+        .iter()
+        .filter(|candidate| candidate.later_cost > play.later_cost)
+        .filter(|candidate| {
+            !matches!(
+                (candidate.confidence, min_confidence),
+                (Some(confidence), Some(min)) if confidence < min
+            )
+        })
+        .max_by_key(|candidate| candidate.score)
+        .filter(|richest| richest.score > play.score);
+    if let Some(richest) = richest {
+        let mut saved = spent(richest);
+        for ch in spent(play) {
+            if let Some(pos) = saved.iter().position(|&held| held == ch) {
+                saved.remove(pos);
+            }
+        }
+        if !saved.is_empty() {
+            return Some(format!(
+                "Play {} ({}) now and hold {} for {held_for}: {} scores {} but spends about {:.0} points of later value",
+                play.word,
+                play.score,
+                saved.iter().collect::<String>(),
+                richest.word,
+                richest.score,
+                richest.later_cost - play.later_cost,
+            ));
+        }
+    }
+
+    let letters: String = spent(play).into_iter().collect();
+    (!letters.is_empty()).then(|| {
+        format!(
+            "Play {letters} now in {} ({}): holding it for {held_for} is worth only about {:.0} points",
+            play.word, play.score, play.later_cost,
+        )
+    })
+}
+
+/// Fills in the rack, round, profile, bonuses and slot length a request leaves out
+/// from the running game, using `slot_index` or the first open slot. A
+/// `slot_index` can't be combined with explicit bonuses or slot length.
+fn with_session_defaults(
+    mut request: SolveRackRequest,
+    session: &GameSession,
+) -> Result<SolveRackRequest, String> {
+    if session.is_finished() {
+        return Ok(request);
+    }
+    if request.rack_letters.is_empty() {
+        request.rack_letters = session.rack().map(<[String]>::to_vec).unwrap_or_default();
+    }
+    request.round.get_or_insert(session.round());
+    request
+        .scoring_profile
+        .get_or_insert_with(|| session.card().profile().name.to_string());
+    let custom_slot = !request.rack_bonuses.is_empty() || request.slot_length.is_some();
+    let slot = match request.slot_index {
+        Some(_) if custom_slot => {
+            return Err("Pass either slot_index or rack_bonuses and slot_length, not both.".into())
+        }
+        Some(slot) => Some(usize::from(slot)),
+        None => session.next_open_slot(),
+    };
+    let layouts = session.layouts(session.round());
+    let layout = match slot {
+        Some(slot) => Some(layouts.get(slot).ok_or_else(|| {
+            format!(
+                "Slot index must be between 0 and {}.",
+                game::SLOTS_PER_ROUND - 1
+            )
+        })?),
+        None => None,
+    };
+    if let Some(layout) = layout {
+        if !custom_slot {
+            request.rack_bonuses = layout
+                .bonuses
+                .iter()
+                .map(|bonus| bonus.as_code().to_string())
+                .collect();
+            request.slot_length = layout.slot_length.map(|len| len as u8);
+        }
+    }
+    Ok(request)
+}
+
+fn slot_layouts(slots: &[SlotLayoutRequest]) -> Result<Vec<planner::SlotLayout>, String> {
+    if slots.len() != game::SLOTS_PER_ROUND {
+        return Err(format!(
+            "Describe all {} slots of the round.",
+            game::SLOTS_PER_ROUND
+        ));
+    }
+    slots
+        .iter()
+        .map(|slot| {
+            if slot.slot_length.is_some_and(|len| !(2..=15).contains(&len)) {
+                return Err("Slot length must be between 2 and 15.".to_string());
+            }
+            if let Some(len) = slot.slot_length {
+                if slot.bonuses.len() > usize::from(len) {
+                    return Err(format!(
+                        "A slot has {len} squares but {} bonuses were given.",
+                        slot.bonuses.len()
+                    ));
+                }
+            }
+            Ok(planner::SlotLayout {
+                bonuses: slot
+                    .bonuses
+                    .iter()
+                    .map(|value| Bonus::from_str_raw(value))
+                    .collect(),
+                slot_length: slot.slot_length.map(usize::from),
+            })
+        })
+        .collect()
+}
+
+#[tauri::command]
+fn plan_round_command(
+    request: PlanRoundRequest,
+    blocklist: State<'_, Blocklist>,
+    draws: State<'_, LetterDraws>,
+) -> Result<PlanRoundResponse, String> {
+    let mut inputs = normalize_request(request.context)?;
+    if inputs.tiles.is_empty() {
+        return Err("Add at least one rack letter before planning.".into());
+    }
+    let layouts = slot_layouts(&request.slots)?;
+    let mut filled = [false; game::SLOTS_PER_ROUND];
+    for &slot in &request.filled_slots {
+        *filled.get_mut(usize::from(slot)).ok_or_else(|| {
+            format!(
+                "Filled slot indices must be between 0 and {}.",
+                game::SLOTS_PER_ROUND - 1
+            )
+        })? = true;
+    }
+    inputs
+        .invalid
+        .extend(lock_store(&blocklist.0)?.words().iter().cloned());
+    let bag = lock_draws(&draws)?.bag(inputs.letter_model);
+
+    let plan = planner::plan_round(
+        &planner::PlanInputs {
+            tiles: &inputs.tiles,
+            target_length: inputs.target_length(),
+            invalid: &inputs.invalid,
+            profile: inputs.profile,
+            round: inputs.round,
+            bag: &bag,
+        },
+        &layouts,
+        &filled,
+    );
+
+    let slots: Vec<SlotPlanReport> = plan
+        .options
+        .into_iter()
+        .map(|option| SlotPlanReport {
+            slot_index: option.slot as u8,
+            advantage: option.best.as_ref().map(|_| option.advantage),
+            best: option.best.map(|candidate| WordRecommendation {
+                slot_index: Some(option.slot as u8),
+                ..recommendation(candidate)
+            }),
+            later_value: option.later_value,
+            completion_chance: option.completion_chance,
+        })
+        .collect();
+    let recommended = plan
+        .recommended
+        .and_then(|slot| {
+            slots
+                .iter()
+                .find(|entry| usize::from(entry.slot_index) == slot)
+        })
+        .and_then(|entry| entry.best.clone());
+
+    Ok(PlanRoundResponse {
+        recommended_slot: plan.recommended.map(|slot| slot as u8),
+        recommendation: recommended,
+        completes_round: plan.completes_round,
+        slots,
+        notes: plan.notes,
+    })
+}
+
+/// Async so the trials, which score thousands of racks, run on a blocking
+/// thread instead of the main one.
+#[tauri::command]
+async fn simulate_reroll_command(
+    request: SimulateRerollRequest,
+    blocklist: State<'_, Blocklist>,
+    draws: State<'_, LetterDraws>,
+) -> Result<RerollSimulationResponse, String> {
+    let mut inputs = normalize_request(request.context)?;
+    if inputs.tiles.is_empty() {
+        return Err("Add at least one rack letter before simulating.".into());
+    }
+    let keep = request
+        .keep_letters
+        .iter()
+        .map(|entry| {
+            let entry = entry.trim().to_ascii_uppercase();
+            let mut chars = entry.chars();
+            match (chars.next(), chars.next()) {
+                (Some(ch), None) if ch.is_ascii_uppercase() => Ok(ch),
+                _ => Err(format!(
+                    "Keep letters must be single letters, got '{entry}'."
+                )),
+            }
+        })
+        .collect::<Result<Vec<char>, String>>()?;
+    inputs
+        .invalid
+        .extend(lock_store(&blocklist.0)?.words().iter().cloned());
+
+    // Seeds are u32 so they survive a round trip through JS numbers.
+    let seed = request.seed.unwrap_or_else(rand::random::<u32>);
+    let trials = request.trials.unwrap_or(simulator::DEFAULT_TRIALS);
+    let bag = lock_draws(&draws)?.bag(inputs.letter_model);
+    let report = tauri::async_runtime::spawn_blocking(move || {
+        let scoring = inputs.score_context();
+        simulator::simulate_reroll(
+            &reroll_ev::RerollInputs {
+                tiles: &inputs.tiles,
+                target_length: inputs.target_length(),
+                invalid: &inputs.invalid,
+                scoring: &scoring,
+                bag: &bag,
+            },
+            &keep,
+            trials,
+            seed,
+        )
+    })
+    .await
+    .map_err(|err| format!("Failed to run the reroll simulation: {err}"))??;
+
+    Ok(RerollSimulationResponse {
+        keep_letters: report.keep.iter().map(char::to_string).collect(),
+        reroll_letters: report.reroll.iter().map(char::to_string).collect(),
+        trials: report.trials,
+        seed: report.seed,
+        current_best: f64::from(report.current_best),
+        mean: report.mean,
+        percentiles: report
+            .percentiles
+            .into_iter()
+            .map(|(percentile, score)| ScorePercentile {
+                percentile,
+                score: f64::from(score),
+            })
+            .collect(),
+        beat_probability: report.beat_probability,
+    })
+}
+
+#[tauri::command]
+fn explain_word_command(
+    request: ExplainWordRequest,
+    blocklist: State<'_, Blocklist>,
+) -> Result<ExplainWordResponse, String> {
+    let word = request.word.trim().to_ascii_uppercase();
+    if word.is_empty() {
+        return Err("Enter a word to explain.".into());
+    }
+    let inputs = normalize_request(request.context)?;
+    let user_blocklist = lock_store(&blocklist.0)?.words().clone();
+
+    let explanation = explain::explain_word(
+        &dictionary::current(),
+        &word,
+        &inputs.tiles,
+        inputs.target_length(),
+        &inputs.invalid,
+        &user_blocklist,
+        &inputs.score_context(),
+    )?;
+
+    Ok(ExplainWordResponse {
+        included: explanation.exclusions.is_empty(),
+        in_dictionary: explanation.in_dictionary,
+        from_allowlist: explanation.from_allowlist,
+        exclusions: explanation
+            .exclusions
+            .iter()
+            .map(|exclusion| WordExclusion {
+                code: exclusion.as_code().to_string(),
+                message: exclusion.describe().to_string(),
+            })
+            .collect(),
+        score: explanation.score.map(f64::from),
+        word,
+    })
+}
+
+#[tauri::command]
+fn score_game_command(request: ScoreGameRequest) -> Result<GameScoreResponse, String> {
+    let mut card = Scorecard::new(resolve_profile(request.scoring_profile.as_deref())?);
+    for play in &request.plays {
+        let bonuses: Vec<Bonus> = play
+            .bonuses
+            .iter()
+            .map(|value| Bonus::from_str_raw(value))
+            .collect();
+        card.play(
+            play.round,
+            usize::from(play.slot_index),
+            &play.word,
+            &Placement {
+                bonuses: &bonuses,
+                ..Placement::default()
+            },
+        )?;
+    }
+    Ok(game_score_response(&card))
+}
+
+fn game_score_response(card: &Scorecard) -> GameScoreResponse {
+    let rounds = card
+        .rounds()
+        .into_iter()
+        .map(|round| RoundScoreReport {
+            round: round.round,
+            multiplier: round.multiplier,
+            slots: round
+                .slot_scores
+                .iter()
+                .enumerate()
+                .map(|(slot, score)| SlotScoreReport {
+                    slot_index: slot as u8,
+                    word: card
+                        .slot(round.round, slot)
+                        .map(|played| played.word.clone()),
+                    score: score.map(f64::from),
+                })
+                .collect(),
+            words_total: f64::from(round.words_total),
+            completion_bonus: f64::from(round.completion_bonus),
+            total: f64::from(round.total),
+        })
+        .collect();
+    GameScoreResponse {
+        scoring_profile: card.profile().name.to_string(),
+        rounds,
+        total: f64::from(card.total()),
+    }
+}
+
+/// Starts a new game. A game still in progress is only replaced when the
+/// request sets `discard_open_game`.
+#[tauri::command]
+fn start_game_command(
+    request: StartGameRequest,
+    game: State<'_, ActiveGame>,
+) -> Result<GameSessionResponse, String> {
+    let profile = resolve_profile(request.scoring_profile.as_deref())?;
+    let layouts = slot_layouts(&request.slots)?;
+    let mut current = lock_game(&game)?;
+    if !request.discard_open_game
+        && current
+            .as_ref()
+            .is_some_and(|session| !session.is_finished())
+    {
+        return Err(
+            "A game is still in progress; finish it, or start again with discard_open_game to drop it."
+                .into(),
+        );
+    }
+    let session = GameSession::start(
+        request.player.as_deref().unwrap_or_default(),
+        profile,
+        layouts,
+    );
+    let response = game_session_response(&session);
+    *current = Some(session);
+    Ok(response)
+}
+
+#[tauri::command]
+fn game_session_command(
+    game: State<'_, ActiveGame>,
+) -> Result<Option<GameSessionResponse>, String> {
+    Ok(lock_game(&game)?.as_ref().map(game_session_response))
+}
+
+/// Moves to the next round. Without `slots` the round keeps the current layouts.
+#[tauri::command]
+fn advance_round_command(
+    slots: Option<Vec<SlotLayoutRequest>>,
+    game: State<'_, ActiveGame>,
+) -> Result<GameSessionResponse, String> {
+    let layouts = slots.as_deref().map(slot_layouts).transpose()?;
+    update_game(&game, |session| session.advance(layouts).map(drop))
+}
+
+/// Plays `word` from the rack last drawn. `slot_offset` pins the 0-based
+/// square it starts on; without it the best-scoring square is used.
+#[tauri::command]
+fn play_word_command(
+    slot_index: u8,
+    word: String,
+    slot_offset: Option<u8>,
+    game: State<'_, ActiveGame>,
+) -> Result<GameSessionResponse, String> {
+    update_game(&game, |session| {
+        session
+            .play(usize::from(slot_index), &word, slot_offset.map(usize::from))
+            .map(drop)
+    })
+}
+
+#[tauri::command]
+fn skip_slot_command(
+    slot_index: u8,
+    game: State<'_, ActiveGame>,
+) -> Result<GameSessionResponse, String> {
+    update_game(&game, |session| session.skip(usize::from(slot_index)))
+}
+
+/// Records a fresh rack, or the whole rack after a reroll, for the next play.
+#[tauri::command]
+fn record_rack_command(
+    rack_letters: Vec<String>,
+    game: State<'_, ActiveGame>,
+) -> Result<GameSessionResponse, String> {
+    let tiles = rack_letters
+        .iter()
+        .map(|entry| {
+            tiles::parse_tile(entry).ok_or_else(|| {
+                format!(
+                    "Rack tiles must be ? or 1 to {} letters, got '{}'.",
+                    tiles::MAX_TILE_LEN,
+                    entry.trim()
+                )
+            })
+        })
+        .collect::<Result<Vec<String>, String>>()?;
+    tiles::check_rack_size(&tiles)?;
+    update_game(&game, |session| session.draw(tiles))
+}
+
+/// Ends the game and appends it to the history log. A logged game can't be
+/// undone; if the log can't be written the game stays open.
+#[tauri::command]
+fn finish_game_command(
+    game: State<'_, ActiveGame>,
+    history: State<'_, GameLog>,
+) -> Result<GameSessionResponse, String> {
+    let mut log = lock_history(&history)?;
+    update_game(&game, |session| {
+        let finished_at = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_secs());
+        log.finish(session, finished_at)
+    })
+}
+
+/// Finished games, oldest first, optionally for one player.
+#[tauri::command]
+fn list_game_history_command(
+    player: Option<String>,
+    history: State<'_, GameLog>,
+) -> Result<Vec<GameRecord>, String> {
+    let log = lock_history(&history)?;
+    Ok(log
+        .for_player(player.as_deref())
+        .into_iter()
+        .cloned()
+        .collect())
+}
+
+/// Whether the game history was read cleanly at startup, and what was
+/// recovered or skipped if not.
+#[tauri::command]
+fn game_history_status_command(
+    history: State<'_, GameLog>,
+) -> Result<GameHistoryStatusResponse, String> {
+    let log = lock_history(&history)?;
+    Ok(GameHistoryStatusResponse {
+        path: log.path().display().to_string(),
+        games: log.for_player(None).len() as u32,
+        recovered: log.recovered().map(str::to_string),
+        damaged: log.damaged().map(str::to_string),
+    })
+}
+
+/// Finished games as a versioned `json` document or `csv` with one row per slot.
+#[tauri::command]
+fn export_game_log_command(
+    format: String,
+    player: Option<String>,
+    history: State<'_, GameLog>,
+) -> Result<String, String> {
+    let log = lock_history(&history)?;
+    let games = log.for_player(player.as_deref());
+    match format.trim().to_ascii_lowercase().as_str() {
+        "json" => game_log::to_json(&games),
+        "csv" => Ok(game_log::to_csv(&games)),
+        other => Err(format!(
+            "Unknown game log format '{other}'; use json or csv."
+        )),
+    }
+}
+
+/// Adds the games in an exported log to the history under new ids, filed by
+/// when they finished. Every word is rescored first, and the games are
+/// written in one append, so nothing is added if any score doesn't match or
+/// the write fails.
+#[tauri::command]
+fn import_game_log_command(
+    contents: String,
+    format: Option<String>,
+    history: State<'_, GameLog>,
+) -> Result<Vec<GameRecord>, String> {
+    let format = format
+        .map(|format| format.trim().to_ascii_lowercase())
+        .unwrap_or_else(|| {
+            if contents.trim_start().starts_with('{') {
+                "json".to_string()
+            } else {
+                "csv".to_string()
+            }
+        });
+    let games = match format.as_str() {
+        "json" => game_log::from_json(&contents)?,
+        "csv" => game_log::from_csv(&contents)?,
+        other => {
+            return Err(format!(
+                "Unknown game log format '{other}'; use json or csv."
+            ))
+        }
+    };
+    let mut log = lock_history(&history)?;
+    let first_id = log.next_id();
+    let imported: Vec<GameRecord> = games
+        .into_iter()
+        .zip(first_id..)
+        .map(|(mut game, id)| {
+            game.id = id;
+            game
+        })
+        .collect();
+    log.append_all(imported.clone())?;
+    Ok(imported)
+}
+
+#[tauri::command]
+fn game_stats_command(
+    player: Option<String>,
+    history: State<'_, GameLog>,
+) -> Result<GameStatsResponse, String> {
+    let log = lock_history(&history)?;
+    let stats = history::stats(&log.for_player(player.as_deref()), BEST_WORD_LIMIT);
+    Ok(GameStatsResponse {
+        player: player.filter(|name| !name.trim().is_empty()),
+        players: log.players(),
+        games: stats.games,
+        average_total: stats.average_total,
+        best_total: f64::from(stats.best_total),
+        completion_rate: stats.completion_rate,
+        rounds: stats
+            .rounds
+            .into_iter()
+            .map(|round| RoundStatsReport {
+                round: round.round,
+                games: round.games,
+                average_total: round.average_total,
+                completion_rate: round.completion_rate,
+            })
+            .collect(),
+        best_words: stats
+            .best_words
+            .into_iter()
+            .map(|best| BestWordReport {
+                game_id: best.game_id,
+                round: best.round,
+                slot_index: best.slot_index,
+                word: best.word,
+                score: f64::from(best.score),
+            })
+            .collect(),
+        trend: stats
+            .trend
+            .into_iter()
+            .map(|point| ScoreTrendPoint {
+                game_id: point.game_id,
+                finished_at: point.finished_at,
+                total: f64::from(point.total),
+                rolling_average: point.rolling_average,
+            })
+            .collect(),
+    })
+}
+
+/// Takes back the last play, skip, round change or finish.
+#[tauri::command]
+fn undo_game_command(game: State<'_, ActiveGame>) -> Result<GameSessionResponse, String> {
+    update_game(&game, GameSession::undo)
+}
+
+#[tauri::command]
+fn redo_game_command(game: State<'_, ActiveGame>) -> Result<GameSessionResponse, String> {
+    update_game(&game, GameSession::redo)
+}
+
+fn update_game(
+    game: &State<'_, ActiveGame>,
+    change: impl FnOnce(&mut GameSession) -> Result<(), String>,
+) -> Result<GameSessionResponse, String> {
+    let mut active = lock_game(game)?;
+    let session = active.as_mut().ok_or("Start a game first.")?;
+    change(session)?;
+    Ok(game_session_response(session))
+}
+
+fn game_session_response(session: &GameSession) -> GameSessionResponse {
+    let round = session.round();
+    let slots = session
+        .layouts(round)
+        .iter()
+        .enumerate()
+        .map(|(slot, layout)| {
+            let played = session.card().slot(round, slot);
+            SessionSlotReport {
+                slot_index: slot as u8,
+                bonuses: layout
+                    .bonuses
+                    .iter()
+                    .map(|bonus| bonus.as_code().to_string())
+                    .collect(),
+                slot_length: layout.slot_length.map(|len| len as u8),
+                status: match session.status(round, slot) {
+                    SlotStatus::Open => "open",
+                    SlotStatus::Played => "played",
+                    SlotStatus::Skipped => "skipped",
+                }
+                .to_string(),
+                word: played.map(|played| played.word.clone()),
+                score: played.map(|played| f64::from(played.score)),
+            }
+        })
+        .collect();
+    GameSessionResponse {
+        round,
+        finished: session.is_finished(),
+        slots,
+        next_open_slot: session.next_open_slot().map(|slot| slot as u8),
+        rack_letters: session.rack().map(<[String]>::to_vec).unwrap_or_default(),
+        score: game_score_response(session.card()),
+        can_undo: session.can_undo(),
+        can_redo: session.can_redo(),
+    }
+}
+
+#[tauri::command]
+fn record_word_outcome_command(
+    word: String,
+    accepted: bool,
+    outcomes: State<'_, WordOutcomes>,
+) -> Result<WordConfidence, String> {
+    let word = word.trim().to_ascii_uppercase();
+    if word.is_empty() || !word.chars().all(|ch| ch.is_ascii_alphabetic()) {
+        return Err("Enter the word the game accepted or rejected.".into());
+    }
+    let mut log = lock_outcomes(&outcomes)?;
+    log.record(&word, accepted)?;
+    Ok(word_confidence(&log, &dictionary::current(), &word))
+}
+
+#[tauri::command]
+fn list_word_outcomes_command(
+    outcomes: State<'_, WordOutcomes>,
+) -> Result<Vec<WordConfidence>, String> {
+    let log = lock_outcomes(&outcomes)?;
+    let dictionary = dictionary::current();
+    Ok(log
+        .entries()
+        .map(|(word, _)| word_confidence(&log, &dictionary, word))
+        .collect())
+}
+
+fn lock_outcomes<'a>(
+    outcomes: &'a State<'_, WordOutcomes>,
+) -> Result<MutexGuard<'a, AcceptanceLog>, String> {
+    outcomes
+        .0
+        .lock()
+        .map_err(|_| "Word history is unavailable after an earlier failure.".to_string())
+}
+
+fn word_confidence(log: &AcceptanceLog, dictionary: &Dictionary, word: &str) -> WordConfidence {
+    let outcomes = log.outcomes(word);
+    WordConfidence {
+        word: word.to_string(),
+        accepted: outcomes.accepted,
+        rejected: outcomes.rejected,
+        confidence: log.confidence(dictionary, word),
+    }
+}
+
+/// Logs the letters of a fresh rack, or only the new letters from a reroll.
+#[tauri::command]
+fn record_letter_draw_command(
+    letters: Vec<String>,
+    draws: State<'_, LetterDraws>,
+) -> Result<LetterCalibrationResponse, String> {
+    let letters = letters
+        .iter()
+        .map(|entry| {
+            let entry = entry.trim().to_ascii_uppercase();
+            let mut chars = entry.chars();
+            match (chars.next(), chars.next()) {
+                (Some(ch), None) if ch.is_ascii_uppercase() => Ok(ch),
+                _ => Err(format!(
+                    "Drawn tiles must be single letters, got '{entry}'."
+                )),
+            }
+        })
+        .collect::<Result<Vec<char>, String>>()?;
+    if letters.is_empty() {
+        return Err("Enter the letters that were drawn.".into());
+    }
+    let mut log = lock_draws(&draws)?;
+    log.record(&letters)?;
+    Ok(letter_calibration(&log))
+}
+
+#[tauri::command]
+fn letter_calibration_command(
+    draws: State<'_, LetterDraws>,
+) -> Result<LetterCalibrationResponse, String> {
+    Ok(letter_calibration(&*lock_draws(&draws)?))
+}
+
+fn lock_draws<'a>(draws: &'a State<'_, LetterDraws>) -> Result<MutexGuard<'a, DrawLog>, String> {
+    draws
+        .0
+        .lock()
+        .map_err(|_| "Letter draw history is unavailable after an earlier failure.".to_string())
+}
+
+fn lock_game<'a>(
+    game: &'a State<'_, ActiveGame>,
+) -> Result<MutexGuard<'a, Option<GameSession>>, String> {
+    game.0
+        .lock()
+        .map_err(|_| "The game session is unavailable after an earlier failure.".to_string())
+}
+
+/// Replays a logged game through the solver and reports, slot by slot, the
+/// best word the rack allowed and the points left behind.
+#[tauri::command]
+fn review_game_command(
+    game_id: u64,
+    blocklist: State<'_, Blocklist>,
+    history: State<'_, GameLog>,
+) -> Result<GameReviewResponse, String> {
+    let record = lock_history(&history)?
+        .find(game_id)
+        .cloned()
+        .ok_or_else(|| format!("No logged game has id {game_id}."))?;
+    let profile = resolve_profile(Some(&record.scoring_profile))?;
+    let invalid: HashSet<String> = lock_store(&blocklist.0)?.words().iter().cloned().collect();
+    let review = review::review_game(&record, profile, &invalid);
+    Ok(GameReviewResponse {
+        game_id,
+        played_total: f64::from(record.total),
+        points_lost: f64::from(review.lost),
+        slots: review
+            .slots
+            .into_iter()
+            .map(|slot| SlotReviewReport {
+                round: slot.round,
+                slot_index: slot.slot_index,
+                rack_letters: slot.rack,
+                played_word: slot.played_word,
+                played_score: f64::from(slot.played_score),
+                optimal: slot.optimal.map(|(word, breakdown)| {
+                    recommendation(solver::RackCandidate {
+                        from_allowlist: dictionary::current().is_allowlisted(&word),
+                        word,
+                        score: breakdown.total,
+                        breakdown,
+                        confidence: None,
+                        later_cost: 0.0,
+                    })
+                }),
+                points_lost: f64::from(slot.lost),
+                mistake: slot.mistake.map(|mistake| mistake.name().to_string()),
+            })
+            .collect(),
+        by_round: review
+            .lost_by_round
+            .into_iter()
+            .map(|(round, lost)| RoundLossReport {
+                round,
+                points_lost: f64::from(lost),
+            })
+            .collect(),
+        by_mistake: review
+            .lost_by_mistake
+            .into_iter()
+            .map(|(mistake, slots, lost)| MistakeLossReport {
+                mistake: mistake.name().to_string(),
+                slots,
+                points_lost: f64::from(lost),
+            })
+            .collect(),
+    })
+}
+
+fn lock_history<'a>(
+    history: &'a State<'_, GameLog>,
+) -> Result<MutexGuard<'a, GameHistory>, String> {
+    history
+        .0
+        .lock()
+        .map_err(|_| "Game history is unavailable after an earlier failure.".to_string())
+}
+
+fn letter_calibration(log: &DrawLog) -> LetterCalibrationResponse {
+    let tally = log.tally();
+    let bag = log.bag(LetterModel::Calibrated);
+    LetterCalibrationResponse {
+        draws: tally.draws,
+        observed_tiles: tally.tiles(),
+        letters: calibration::estimate(tally)
+            .into_iter()
+            .zip(bag)
+            .map(|(estimate, bag_count)| LetterEstimateReport {
+                letter: estimate.letter.to_string(),
+                observed: estimate.observed,
+                standard_share: estimate.standard,
+                calibrated_share: estimate.share,
+                low: estimate.low,
+                high: estimate.high,
+                bag_count,
+            })
+            .collect(),
+    }
+}
+
+#[tauri::command]
+fn list_blocked_words_command(blocklist: State<'_, Blocklist>) -> Result<Vec<String>, String> {
+    Ok(lock_store(&blocklist.0)?.list())
+}
+
+#[tauri::command]
+fn add_blocked_words_command(
+    words: Vec<String>,
+    blocklist: State<'_, Blocklist>,
+) -> Result<WordListResponse, String> {
+    let mut store = lock_store(&blocklist.0)?;
+    let changed = store.add(&words)?;
+    Ok(word_list_response(&store, changed))
+}
+
+#[tauri::command]
+fn remove_blocked_words_command(
+    words: Vec<String>,
+    blocklist: State<'_, Blocklist>,
+) -> Result<WordListResponse, String> {
+    let mut store = lock_store(&blocklist.0)?;
+    let changed = store.remove(&words)?;
+    Ok(word_list_response(&store, changed))
+}
+
+#[tauri::command]
+fn import_blocked_words_command(
+    contents: String,
+    blocklist: State<'_, Blocklist>,
+) -> Result<WordListResponse, String> {
+    let mut store = lock_store(&blocklist.0)?;
+    let changed = store.import(&contents)?;
+    Ok(word_list_response(&store, changed))
+}
+
+#[tauri::command]
+fn export_blocked_words_command(blocklist: State<'_, Blocklist>) -> Result<String, String> {
+    Ok(lock_store(&blocklist.0)?.export())
+}
+
+fn lock_store(store: &Mutex<WordStore>) -> Result<MutexGuard<'_, WordStore>, String> {
+    store
+        .lock()
+        .map_err(|_| "Word list is unavailable after an earlier failure.".to_string())
+}
+
+fn word_list_response(store: &WordStore, changed: usize) -> WordListResponse {
+    WordListResponse {
+        words: store.list(),
+        changed,
+        reload_error: None,
+    }
+}
+
+/// The change is already saved, so a failed rebuild is reported alongside it
+/// rather than as an error.
+fn allowlist_response(app: &AppHandle, store: &WordStore, changed: usize) -> WordListResponse {
+    let reload_error = if changed > 0 {
+        reload_dictionary(app, store).err()
+    } else {
+        None
+    };
+    WordListResponse {
+        reload_error,
+        ..word_list_response(store, changed)
+    }
+}
+
+#[tauri::command]
+fn list_allowed_words_command(allowlist: State<'_, Allowlist>) -> Result<Vec<String>, String> {
+    Ok(lock_store(&allowlist.0)?.list())
+}
+
+#[tauri::command]
+fn add_allowed_words_command(
+    app: AppHandle,
+    words: Vec<String>,
+    allowlist: State<'_, Allowlist>,
+) -> Result<WordListResponse, String> {
+    let mut store = lock_store(&allowlist.0)?;
+    let changed = store.add(&words)?;
+    Ok(allowlist_response(&app, &store, changed))
+}
+
+#[tauri::command]
+fn remove_allowed_words_command(
+    app: AppHandle,
+    words: Vec<String>,
+    allowlist: State<'_, Allowlist>,
+) -> Result<WordListResponse, String> {
+    let mut store = lock_store(&allowlist.0)?;
+    let changed = store.remove(&words)?;
+    Ok(allowlist_response(&app, &store, changed))
+}
+
+#[tauri::command]
+fn import_allowed_words_command(
+    app: AppHandle,
+    contents: String,
+    allowlist: State<'_, Allowlist>,
+) -> Result<WordListResponse, String> {
+    let mut store = lock_store(&allowlist.0)?;
+    let changed = store.import(&contents)?;
+    Ok(allowlist_response(&app, &store, changed))
+}
+
+#[tauri::command]
+fn export_allowed_words_command(allowlist: State<'_, Allowlist>) -> Result<String, String> {
+    Ok(lock_store(&allowlist.0)?.export())
+}
+
+#[tauri::command]
+fn reload_dictionary_command(
+    app: AppHandle,
+    allowlist: State<'_, Allowlist>,
+) -> Result<DictionaryReloadResponse, String> {
+    let store = lock_store(&allowlist.0)?;
+    reload_dictionary(&app, &store)
+}
+
+fn reload_dictionary(
+    app: &AppHandle,
+    allowlist: &WordStore,
+) -> Result<DictionaryReloadResponse, String> {
+    let dir = dictionary_dir(app)?;
+    let generation = dictionary::next_generation();
+    let loaded = Dictionary::load(&dir, allowlist.words())?;
+    let response = dictionary_report(&dir, &loaded)?;
+    dictionary::install(generation, loaded);
+    Ok(response)
+}
+
+fn app_data_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    app.path()
+        .app_data_dir()
+        .map_err(|err| format!("Unable to resolve the app data directory: {err}"))
+}
+
+fn dictionary_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    app_data_dir(app).map(|dir| dir.join(DICTIONARY_DIR))
+}
+
+fn dictionary_report(dir: &Path, loaded: &Dictionary) -> Result<DictionaryReloadResponse, String> {
+    let loaded_sources = loaded.sources()?;
+    let sources = SourceKind::ALL
+        .iter()
+        .map(|&kind| {
+            let source = loaded_sources.get(kind);
+            DictionarySourceReport {
+                source: kind.as_code().to_string(),
+                runtime: source.is_runtime(),
+                path: source
+                    .is_runtime()
+                    .then(|| source.path().display().to_string()),
+                word_count: source.word_count(),
+            }
+        })
+        .collect();
+
+    Ok(DictionaryReloadResponse {
+        directory: dir.display().to_string(),
+        dictionary_words: loaded.word_count(),
+        allowlisted_words: loaded.allowlisted_count(),
+        sources,
     })
 }
 
@@ -138,11 +1535,89 @@ fn solve_rack_command(request: SolveRackRequest) -> Result<SolveRackResponse, St
 pub fn run() {
     tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
-        .invoke_handler(tauri::generate_handler![solve_rack_command])
+        .invoke_handler(tauri::generate_handler![
+            solve_rack_command,
+            plan_round_command,
+            simulate_reroll_command,
+            explain_word_command,
+            score_game_command,
+            start_game_command,
+            game_session_command,
+            advance_round_command,
+            play_word_command,
+            skip_slot_command,
+            record_rack_command,
+            finish_game_command,
+            list_game_history_command,
+            game_history_status_command,
+            export_game_log_command,
+            import_game_log_command,
+            game_stats_command,
+            review_game_command,
+            undo_game_command,
+            redo_game_command,
+            record_word_outcome_command,
+            list_word_outcomes_command,
+            record_letter_draw_command,
+            letter_calibration_command,
+            reload_dictionary_command,
+            list_blocked_words_command,
+            add_blocked_words_command,
+            remove_blocked_words_command,
+            import_blocked_words_command,
+            export_blocked_words_command,
+            list_allowed_words_command,
+            add_allowed_words_command,
+            remove_allowed_words_command,
+            import_allowed_words_command,
+            export_allowed_words_command
+        ])
         .setup(|app| {
             if let Some(window) = app.get_webview_window("main") {
                 let _ = window.maximize();
             }
+            let data_dir = app_data_dir(app.handle())?;
+            if let Ok(resources) = app.path().resource_dir() {
+                dictionary::set_bundled_dir(resources.join(DICTIONARY_DIR));
+            }
+            let blocklist = WordStore::open(
+                data_dir.join(BLOCKLIST_FILE),
+                "Words the game rejected. One word per line; lines starting with # are ignored.",
+            )?;
+            app.manage(Blocklist(Mutex::new(blocklist)));
+
+            let allowlist = WordStore::open(
+                data_dir.join(ALLOWLIST_FILE),
+                "Words the game accepts that the bundled lists reject. One word per line.",
+            )?;
+            let allowed_words = allowlist.words().clone();
+            app.manage(Allowlist(Mutex::new(allowlist)));
+            app.manage(WordOutcomes(Mutex::new(AcceptanceLog::open(
+                data_dir.join(WORD_OUTCOMES_FILE),
+            )?)));
+            app.manage(LetterDraws(Mutex::new(DrawLog::open(
+                data_dir.join(LETTER_DRAWS_FILE),
+            )?)));
+            app.manage(ActiveGame(Mutex::new(None)));
+            // A damaged log is reported by `game_history_status_command`
+            // rather than stopping startup.
+            app.manage(GameLog(Mutex::new(GameHistory::open(
+                data_dir.join(GAME_HISTORY_FILE),
+            ))));
+
+            // The embedded dictionary serves solves until this load lands; a
+            // reload that starts meanwhile is newer and wins.
+            let dir = data_dir.join(DICTIONARY_DIR);
+            let generation = dictionary::next_generation();
+            let handle = app.handle().clone();
+            std::thread::spawn(move || match Dictionary::load(&dir, &allowed_words) {
+                Ok(loaded) => {
+                    dictionary::install(generation, loaded);
+                }
+                Err(err) => {
+                    let _ = handle.emit(DICTIONARY_LOAD_FAILED_EVENT, err);
+                }
+            });
             Ok(())
         })
         .run(tauri::generate_context!())
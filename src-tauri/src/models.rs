@@ -15,12 +15,54 @@ pub struct WordRecommendation {
     #[serde(default)]
     pub letters_used: Vec<String>,
     #[serde(default)]
+    pub blank_letters: Vec<BlankAssignment>,
+    #[serde(default)]
+    pub slot_offset: Option<u8>,
+    #[serde(default)]
+    pub covered_squares: Vec<u8>,
+    #[serde(default)]
     pub placement_notes: Option<String>,
+    #[serde(default)]
+    pub score_breakdown: Option<ScoreBreakdownReport>,
+    #[serde(default)]
+    pub from_allowlist: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub struct LetterScoreReport {
+    pub letter: String,
+    pub base: u32,
+    pub bonus: String,
+    pub letter_multiplier: u32,
+    pub value: u32,
+    #[serde(default)]
+    pub blank: bool,
+}
+
+/// Which letter a `?` tile from the rack stands for, by position in the word.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub struct BlankAssignment {
+    pub position: u8,
+    pub letter: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub struct ScoreBreakdownReport {
+    #[serde(default)]
+    pub letters: Vec<LetterScoreReport>,
+    pub letter_sum: u32,
+    pub word_multiplier: u32,
+    pub round_multiplier: u32,
+    pub total: u32,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub struct SolveRackRequest {
+    #[serde(default)]
     pub rack_letters: Vec<String>,
     #[serde(default)]
     pub target_word_length: Option<u8>,
@@ -30,6 +72,38 @@ pub struct SolveRackRequest {
     pub rack_bonuses: Vec<String>,
     #[serde(default)]
     pub round: Option<u8>,
+    #[serde(default)]
+    pub min_confidence: Option<f64>,
+    #[serde(default)]
+    pub scoring_profile: Option<String>,
+    #[serde(default)]
+    pub slot_length: Option<u8>,
+    /// `standard` (default) or `calibrated` draw odds.
+    #[serde(default)]
+    pub letter_model: Option<String>,
+    /// Rerolls left before the slot must be played; defaults to 2.
+    #[serde(default)]
+    pub rerolls_remaining: Option<u8>,
+    /// Most tiles the expected-value advice may reroll; defaults to 3 and is
+    /// capped at the rack size and at 7. Rerolls of up to 3 tiles score every
+    /// draw exactly, wider ones are estimated from sampled draws.
+    #[serde(default)]
+    pub max_reroll_tiles: Option<u8>,
+    /// Value keep sets by the best play over every reroll left instead of
+    /// the next draw only. Each reroll beyond the first makes the advice
+    /// about five times slower, so it is off by default.
+    #[serde(default)]
+    pub plan_rerolls: bool,
+    /// Weigh what power letters could earn on a TL in a later slot, this
+    /// round or a later one, when ranking words and advising rerolls, and
+    /// explain the trade-off in `notes`. On by default.
+    #[serde(default)]
+    pub hold_power_letters: Option<bool>,
+    /// Slot of the current game session whose bonuses and length to solve
+    /// for; defaults to the first open slot. Can't be combined with
+    /// `rack_bonuses` or `slot_length`.
+    #[serde(default)]
+    pub slot_index: Option<u8>,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -43,9 +117,17 @@ pub struct SolveRackResponse {
     #[serde(default)]
     pub round: Option<u8>,
     #[serde(default)]
+    pub slot_length: Option<u8>,
+    pub scoring_profile: String,
+    pub letter_model: String,
+    #[serde(default)]
     pub recommendations: Vec<WordRecommendation>,
     #[serde(default)]
     pub reroll_suggestions: Vec<RerollSuggestion>,
+    /// Strategy trade-offs behind the ranking, such as letters held back
+    /// for a later round.
+    #[serde(default)]
+    pub notes: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -69,3 +151,383 @@ pub struct RerollSuggestion {
     #[serde(default)]
     pub focus_tags: Vec<String>,
 }
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct DictionarySourceReport {
+    pub source: String,
+    pub runtime: bool,
+    #[serde(default)]
+    pub path: Option<String>,
+    pub word_count: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct DictionaryReloadResponse {
+    pub directory: String,
+    pub dictionary_words: usize,
+    pub allowlisted_words: usize,
+    #[serde(default)]
+    pub sources: Vec<DictionarySourceReport>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct WordListResponse {
+    #[serde(default)]
+    pub words: Vec<String>,
+    pub changed: usize,
+    /// Set when an allowlist change was saved but the dictionary could not
+    /// be rebuilt with it; the change applies on the next successful reload.
+    #[serde(default)]
+    pub reload_error: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct ExplainWordRequest {
+    pub word: String,
+    #[serde(flatten)]
+    pub context: SolveRackRequest,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct WordExclusion {
+    pub code: String,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct ExplainWordResponse {
+    pub word: String,
+    pub included: bool,
+    pub in_dictionary: bool,
+    pub from_allowlist: bool,
+    #[serde(default)]
+    pub exclusions: Vec<WordExclusion>,
+    #[serde(default)]
+    pub score: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct WordConfidence {
+    pub word: String,
+    pub accepted: u32,
+    pub rejected: u32,
+    pub confidence: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct PlayedSlot {
+    pub round: u8,
+    pub slot_index: u8,
+    pub word: String,
+    #[serde(default)]
+    pub bonuses: Vec<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct ScoreGameRequest {
+    #[serde(default)]
+    pub scoring_profile: Option<String>,
+    #[serde(default)]
+    pub plays: Vec<PlayedSlot>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct SlotScoreReport {
+    pub slot_index: u8,
+    #[serde(default)]
+    pub word: Option<String>,
+    #[serde(default)]
+    pub score: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct RoundScoreReport {
+    pub round: u8,
+    pub multiplier: u32,
+    pub slots: Vec<SlotScoreReport>,
+    pub words_total: f64,
+    pub completion_bonus: f64,
+    pub total: f64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct GameScoreResponse {
+    pub scoring_profile: String,
+    pub rounds: Vec<RoundScoreReport>,
+    pub total: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub struct SlotLayoutRequest {
+    #[serde(default)]
+    pub bonuses: Vec<String>,
+    #[serde(default)]
+    pub slot_length: Option<u8>,
+}
+
+/// Plans one play across the round's slots. `rack_bonuses` and
+/// `slot_length` in the shared context are ignored in favour of `slots`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct PlanRoundRequest {
+    #[serde(flatten)]
+    pub context: SolveRackRequest,
+    #[serde(default)]
+    pub slots: Vec<SlotLayoutRequest>,
+    #[serde(default)]
+    pub filled_slots: Vec<u8>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct SlotPlanReport {
+    pub slot_index: u8,
+    #[serde(default)]
+    pub best: Option<WordRecommendation>,
+    pub later_value: f64,
+    pub advantage: Option<f64>,
+    /// Chance the rest of the round still gets filled if this slot is used now.
+    pub completion_chance: f64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct PlanRoundResponse {
+    #[serde(default)]
+    pub recommended_slot: Option<u8>,
+    #[serde(default)]
+    pub recommendation: Option<WordRecommendation>,
+    pub completes_round: bool,
+    #[serde(default)]
+    pub slots: Vec<SlotPlanReport>,
+    #[serde(default)]
+    pub notes: Vec<String>,
+}
+
+/// Reroll everything but `keep_letters` `trials` times. Pass `seed` to replay
+/// an earlier run; the response echoes the seed it used.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct SimulateRerollRequest {
+    #[serde(flatten)]
+    pub context: SolveRackRequest,
+    #[serde(default)]
+    pub keep_letters: Vec<String>,
+    #[serde(default)]
+    pub trials: Option<u32>,
+    #[serde(default)]
+    pub seed: Option<u32>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct ScorePercentile {
+    pub percentile: u8,
+    pub score: f64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct RerollSimulationResponse {
+    pub keep_letters: Vec<String>,
+    pub reroll_letters: Vec<String>,
+    pub trials: u32,
+    pub seed: u32,
+    pub current_best: f64,
+    pub mean: f64,
+    pub percentiles: Vec<ScorePercentile>,
+    pub beat_probability: f64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct LetterEstimateReport {
+    pub letter: String,
+    pub observed: u32,
+    pub standard_share: f64,
+    pub calibrated_share: f64,
+    pub low: f64,
+    pub high: f64,
+    /// Tiles of this letter in the calibrated bag.
+    pub bag_count: u8,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct LetterCalibrationResponse {
+    pub draws: u32,
+    pub observed_tiles: u32,
+    #[serde(default)]
+    pub letters: Vec<LetterEstimateReport>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct StartGameRequest {
+    /// Whose game this is, for per-player history.
+    #[serde(default)]
+    pub player: Option<String>,
+    #[serde(default)]
+    pub scoring_profile: Option<String>,
+    #[serde(default)]
+    pub slots: Vec<SlotLayoutRequest>,
+    /// Throw away a game that is still in progress instead of refusing to
+    /// start a new one.
+    #[serde(default)]
+    pub discard_open_game: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct SessionSlotReport {
+    pub slot_index: u8,
+    #[serde(default)]
+    pub bonuses: Vec<String>,
+    #[serde(default)]
+    pub slot_length: Option<u8>,
+    /// `open`, `played` or `skipped`.
+    pub status: String,
+    #[serde(default)]
+    pub word: Option<String>,
+    #[serde(default)]
+    pub score: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct GameSessionResponse {
+    pub round: u8,
+    pub finished: bool,
+    /// Slots of the current round.
+    #[serde(default)]
+    pub slots: Vec<SessionSlotReport>,
+    #[serde(default)]
+    pub next_open_slot: Option<u8>,
+    /// The rack as last recorded for the next play.
+    #[serde(default)]
+    pub rack_letters: Vec<String>,
+    pub score: GameScoreResponse,
+    pub can_undo: bool,
+    pub can_redo: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct RoundStatsReport {
+    pub round: u8,
+    pub games: u32,
+    pub average_total: f64,
+    pub completion_rate: f64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct BestWordReport {
+    pub game_id: u64,
+    pub round: u8,
+    pub slot_index: u8,
+    pub word: String,
+    pub score: f64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct ScoreTrendPoint {
+    pub game_id: u64,
+    pub finished_at: u64,
+    pub total: f64,
+    pub rolling_average: f64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct GameStatsResponse {
+    #[serde(default)]
+    pub player: Option<String>,
+    #[serde(default)]
+    pub players: Vec<String>,
+    pub games: u32,
+    pub average_total: f64,
+    pub best_total: f64,
+    /// Share of rounds played that earned the +100 completion bonus.
+    pub completion_rate: f64,
+    #[serde(default)]
+    pub rounds: Vec<RoundStatsReport>,
+    #[serde(default)]
+    pub best_words: Vec<BestWordReport>,
+    #[serde(default)]
+    pub trend: Vec<ScoreTrendPoint>,
+}
+
+/// What happened when the game history was read at startup.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct GameHistoryStatusResponse {
+    pub path: String,
+    pub games: u32,
+    /// A torn last line moved aside after a crash.
+    #[serde(default)]
+    pub recovered: Option<String>,
+    /// Lines that were skipped, or why the log couldn't be read at all.
+    #[serde(default)]
+    pub damaged: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct SlotReviewReport {
+    pub round: u8,
+    pub slot_index: u8,
+    #[serde(default)]
+    pub rack_letters: Vec<String>,
+    pub played_word: String,
+    pub played_score: f64,
+    #[serde(default)]
+    pub optimal: Option<WordRecommendation>,
+    pub points_lost: f64,
+    /// `ignored_dw`, `missed_tl_hitter`, `wrong_length` or `weaker_word`.
+    #[serde(default)]
+    pub mistake: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct RoundLossReport {
+    pub round: u8,
+    pub points_lost: f64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct MistakeLossReport {
+    pub mistake: String,
+    pub slots: u32,
+    pub points_lost: f64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct GameReviewResponse {
+    pub game_id: u64,
+    pub played_total: f64,
+    pub points_lost: f64,
+    #[serde(default)]
+    pub slots: Vec<SlotReviewReport>,
+    #[serde(default)]
+    pub by_round: Vec<RoundLossReport>,
+    #[serde(default)]
+    pub by_mistake: Vec<MistakeLossReport>,
+}
@@ -0,0 +1,326 @@
+use std::collections::HashSet;
+
+use crate::anagram;
+use crate::dictionary;
+use crate::game::COMPLETION_BONUS;
+use crate::scoring::{Bonus, ScoreContext, ScoringProfile};
+use crate::solver::{self, RackCandidate};
+
+/// Longest words counted when estimating whether a later rack can fill a
+/// slot. Short words fit every slot, so longer ones barely move the odds.
+const FILL_WORD_LEN: usize = 4;
+
+/// Bonus squares of one slot in the round.
+#[derive(Debug, Clone, Default)]
+pub struct SlotLayout {
+    pub bonuses: Vec<Bonus>,
+    pub slot_length: Option<usize>,
+}
+
+#[derive(Debug, Clone)]
+pub struct SlotOption {
+    pub slot: usize,
+    pub best: Option<RackCandidate>,
+    /// What an average draw of the same length would score here later.
+    pub later_value: f64,
+    /// Points gained by using this slot now instead of saving it.
+    pub advantage: f64,
+    /// Chance the other open slots all get a word later, so the round
+    /// still earns its completion bonus.
+    pub completion_chance: f64,
+    /// `advantage` plus the completion bonus weighted by `completion_chance`.
+    pub value: f64,
+}
+
+#[derive(Debug, Clone)]
+pub struct RoundPlan {
+    pub options: Vec<SlotOption>,
+    pub recommended: Option<usize>,
+    pub completes_round: bool,
+    pub notes: Vec<String>,
+}
+
+/// The rack, round and filters shared by every slot in a plan.
+pub struct PlanInputs<'a> {
+    pub tiles: &'a [String],
+    pub target_length: Option<usize>,
+    pub invalid: &'a HashSet<String>,
+    pub profile: &'a ScoringProfile,
+    pub round: u8,
+    /// Tile counts an average future draw comes from.
+    pub bag: &'a [u8; 26],
+}
+
+/// Picks the open slot where the rack's best word beats an average future
+/// play by the most, so premium slots are kept for racks that deserve them.
+/// Each choice also carries the completion bonus, weighted by the chance
+/// later racks fill every other open slot, so slots that are hard to fill
+/// are taken while a word fits them.
+pub fn plan_round(inputs: &PlanInputs, layouts: &[SlotLayout], filled: &[bool]) -> RoundPlan {
+    let open: Vec<usize> = (0..layouts.len())
+        .filter(|&slot| !filled.get(slot).copied().unwrap_or(false))
+        .collect();
+    let mut notes: Vec<String> = Vec::new();
+    let fill_chances: Vec<f64> = if open.len() > 1 {
+        open.iter()
+            .map(|&slot| fill_chance(inputs, &layouts[slot]))
+            .collect()
+    } else {
+        vec![1.0; open.len()]
+    };
+
+    let options: Vec<SlotOption> = open
+        .iter()
+        .map(|&slot| {
+            let layout = &layouts[slot];
+            let scoring = ScoreContext {
+                profile: inputs.profile,
+                bonuses: &layout.bonuses,
+                round: inputs.round,
+                slot_length: layout.slot_length,
+            };
+            let best = solver::solve_rack(
+                inputs.tiles,
+                inputs.target_length,
+                inputs.invalid,
+                1,
+                &scoring,
+                None,
+                None,
+            )
+            .into_iter()
+            .next();
+            let later_value = best.as_ref().map_or(0.0, |candidate| {
+                expected_score(&scoring, inputs.bag, candidate.breakdown.tiles.len())
+            });
+            let advantage = best.as_ref().map_or(f64::NEG_INFINITY, |candidate| {
+                f64::from(candidate.score) - later_value
+            });
+            let completion_chance: f64 = open
+                .iter()
+                .zip(&fill_chances)
+                .filter(|(&other, _)| other != slot)
+                .map(|(_, &chance)| chance)
+                .product();
+            SlotOption {
+                slot,
+                best,
+                later_value,
+                advantage,
+                completion_chance,
+                value: advantage + f64::from(COMPLETION_BONUS) * completion_chance,
+            }
+        })
+        .collect();
+
+    for option in options.iter().filter(|option| option.best.is_none()) {
+        notes.push(format!(
+            "No word from this rack fits slot {}",
+            option.slot + 1
+        ));
+    }
+
+    let playable = options.iter().filter(|option| option.best.is_some());
+    let recommended = playable
+        .clone()
+        .max_by(|a, b| {
+            a.value
+                .total_cmp(&b.value)
+                .then_with(|| b.slot.cmp(&a.slot))
+        })
+        .map(|option| option.slot);
+    let completes_round = open.len() == 1 && recommended.is_some();
+
+    if completes_round {
+        notes.push(format!(
+            "Last open slot: filling it banks the +{COMPLETION_BONUS} round bonus"
+        ));
+    } else if let (Some(chosen), Some(top), Some(by_advantage)) = (
+        recommended.and_then(|slot| options.iter().find(|option| option.slot == slot)),
+        playable
+            .clone()
+            .max_by_key(|option| option.best.as_ref().map(|best| best.score)),
+        playable.max_by(|a, b| {
+            a.advantage
+                .total_cmp(&b.advantage)
+                .then_with(|| b.slot.cmp(&a.slot))
+        }),
+    ) {
+        if by_advantage.slot != chosen.slot {
+            notes.push(format!(
+                "Slot {} is harder to fill later than slot {}; taking it now keeps the +{COMPLETION_BONUS} round bonus within reach",
+                chosen.slot + 1,
+                by_advantage.slot + 1
+            ));
+        } else if top.slot != chosen.slot {
+            notes.push(format!(
+                "Slot {} scores more now, but an average draw is worth {:.0} there later; save it",
+                top.slot + 1,
+                top.later_value
+            ));
+        }
+    }
+
+    RoundPlan {
+        options,
+        recommended,
+        completes_round,
+        notes,
+    }
+}
+
+/// Chance a fresh rack of the same size, drawn from the plan's bag, holds at
+/// least one short word that fits the slot. Words are treated as independent, which overstates the
+/// odds only where many words fit, and those slots are easy to fill anyway.
+fn fill_chance(inputs: &PlanInputs, layout: &SlotLayout) -> f64 {
+    let bag = inputs.bag;
+    let rack_size = inputs.tiles.len();
+    let max_len = layout
+        .slot_length
+        .unwrap_or(FILL_WORD_LEN)
+        .min(rack_size)
+        .min(FILL_WORD_LEN);
+    let drawable = bag.map(|count| count.min(max_len as u8));
+    let dictionary = dictionary::current();
+    let mut miss = 1.0;
+    for len in 1..=max_len {
+        dictionary.for_each_fit(&drawable, 0, Some(len), |word| {
+            if !inputs.invalid.contains(word) {
+                miss *= 1.0 - rack_odds(bag, rack_size, word);
+            }
+        });
+    }
+    1.0 - miss
+}
+
+/// Chance that `rack_size` tiles drawn without replacement from `bag`
+/// include every letter of `word`.
+fn rack_odds(bag: &[u8; 26], rack_size: usize, word: &str) -> f64 {
+    let Some(need) = anagram::signature_of(word) else {
+        return 0.0;
+    };
+    let needed: Vec<(u32, u32)> = (0..26)
+        .filter(|&idx| need[idx] > 0)
+        .map(|idx| (u32::from(bag[idx]), u32::from(need[idx])))
+        .collect();
+    let total: u32 = bag.iter().map(|&count| u32::from(count)).sum();
+    let others = total - needed.iter().map(|&(in_bag, _)| in_bag).sum::<u32>();
+    let draws = rack_size as u32;
+    ways_to_hold(&needed, others, draws) / solver::binomial(total, draws)
+}
+
+/// Draws of `draws` tiles holding at least the needed count of each letter.
+fn ways_to_hold(needed: &[(u32, u32)], others: u32, draws: u32) -> f64 {
+    let Some((&(in_bag, need), rest)) = needed.split_first() else {
+        return solver::binomial(others, draws);
+    };
+    (need..=in_bag.min(draws))
+        .map(|taken| solver::binomial(in_bag, taken) * ways_to_hold(rest, others, draws - taken))
+        .sum()
+}
+
+/// Expected score of a `tiles`-long word drawn from `bag`, placed on the
+/// slot's best squares.
+fn expected_score(scoring: &ScoreContext, bag: &[u8; 26], tiles: usize) -> f64 {
+    let profile = scoring.profile;
+    let mean_letter = profile.mean_letter_value(bag);
+
+    scoring
+        .offsets(tiles)
+        .map(|offset| {
+            let (letters, word_multiplier) = (0..tiles).fold((0.0, 1u32), |(sum, word), idx| {
+                let bonus = scoring
+                    .bonuses
+                    .get(offset + idx)
+                    .copied()
+                    .unwrap_or(Bonus::None);
+                (
+                    sum + mean_letter * f64::from(profile.letter_multiplier(bonus)),
+                    word * profile.word_multiplier(bonus),
+                )
+            });
+            letters * f64::from(word_multiplier) * f64::from(scoring.round_multiplier())
+        })
+        .fold(0.0, f64::max)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scoring::SCRABBLE;
+    use crate::solver::LETTER_BAG_COUNTS;
+
+    #[test]
+    fn saves_premium_slots_for_strong_racks() {
+        let plain = SlotLayout {
+            bonuses: Vec::new(),
+            slot_length: Some(7),
+        };
+        let premium = SlotLayout {
+            bonuses: vec![Bonus::TripleWord],
+            slot_length: Some(7),
+        };
+        let layouts = [plain.clone(), premium, plain.clone(), plain.clone(), plain];
+        let invalid = HashSet::new();
+        let plan = |rack: &str, filled: &[bool]| {
+            let tiles: Vec<String> = rack.chars().map(String::from).collect();
+            let inputs = PlanInputs {
+                tiles: &tiles,
+                target_length: None,
+                invalid: &invalid,
+                profile: &SCRABBLE,
+                round: 1,
+                bag: &LETTER_BAG_COUNTS,
+            };
+            plan_round(&inputs, &layouts, filled)
+        };
+
+        let weak = plan("EEIRSTL", &[false; 5]);
+        assert_ne!(weak.recommended, Some(1));
+        let strong = plan("QUIZJAX", &[false; 5]);
+        assert_eq!(strong.recommended, Some(1));
+
+        let last = plan("EEIRSTL", &[true, true, true, false, true]);
+        assert_eq!(last.recommended, Some(3));
+        assert!(last.completes_round);
+    }
+
+    #[test]
+    fn takes_hard_to_fill_slots_while_a_word_fits() {
+        let open = SlotLayout {
+            bonuses: vec![Bonus::None, Bonus::DoubleWord],
+            slot_length: Some(7),
+        };
+        let short = SlotLayout {
+            bonuses: Vec::new(),
+            slot_length: Some(2),
+        };
+        let layouts = [open.clone(), short, open.clone(), open.clone(), open];
+        // Only AT is left for the two-square slot, so few racks can fill it.
+        let invalid: HashSet<String> = crate::dictionary::current()
+            .words()
+            .filter(|word| word.len() == 2 && *word != "AT")
+            .map(String::from)
+            .collect();
+        let tiles: Vec<String> = "JATXEIS".chars().map(String::from).collect();
+        let inputs = PlanInputs {
+            tiles: &tiles,
+            target_length: None,
+            invalid: &invalid,
+            profile: &SCRABBLE,
+            round: 1,
+            bag: &LETTER_BAG_COUNTS,
+        };
+        let plan = plan_round(&inputs, &layouts, &[true, false, true, true, false]);
+
+        // TAXIES on the DW gains more on its own, but leaving the short slot
+        // open would likely cost the +100.
+        let [short, long] = &plan.options[..] else {
+            panic!("expected two open slots");
+        };
+        assert!(long.advantage > short.advantage);
+        assert!(short.completion_chance > 0.9 && long.completion_chance < 0.5);
+        assert_eq!(plan.recommended, Some(1));
+        assert!(plan.notes[0].contains("+100"));
+    }
+}
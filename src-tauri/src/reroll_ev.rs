@@ -0,0 +1,444 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::scoring::ScoreContext;
+use crate::simulator;
+use crate::solver::{self, RerollAdvice};
+
+/// Default cap on the tiles a keep set may reroll, and the most whose every
+/// draw is enumerated. Each extra tile multiplies the racks to score: on a
+/// 7-tile rack in a release build 3 takes up to a second, 4 about 4 s and 5
+/// about 10 s, and each reroll left after the first multiplies that again by
+/// about five. Wider rerolls are estimated from `SAMPLED_DRAWS` draws.
+pub const MAX_EXACT_REROLLS: usize = 3;
+/// Hard ceiling on `max_reroll_tiles`, whatever the rack holds.
+pub const MAX_REROLL_TILES: usize = 7;
+/// Draws sampled per keep set that rerolls more than `MAX_EXACT_REROLLS` tiles.
+const SAMPLED_DRAWS: u32 = 256;
+/// Fixed so the same rack always gets the same advice.
+const SAMPLE_SEED: u32 = 0x5EED_4EE7;
+
+/// The rack and slot a reroll is judged against.
+pub struct RerollInputs<'a> {
+    pub tiles: &'a [String],
+    pub target_length: Option<usize>,
+    pub invalid: &'a HashSet<String>,
+    pub scoring: &'a ScoreContext<'a>,
+    /// Tile counts replacement letters are drawn from.
+    pub bag: &'a [u8; 26],
+}
+
+/// Widest reroll considered at each step after the first when planning more
+/// than one reroll ahead; every extra step multiplies the racks to score.
+pub const HORIZON_REROLLS: usize = 1;
+
+#[derive(Debug, Clone)]
+pub struct KeepEvaluation {
+    pub keep: Vec<char>,
+    pub reroll: Vec<char>,
+    /// Mean final score over every draw, weighted by its odds, when any
+    /// later rerolls are also played for the best expected value.
+    pub expected_score: f64,
+    /// Chance the next rack beats the best word on the current one.
+    pub improve_probability: f64,
+    /// Rerolls the plan behind `expected_score` uses.
+    pub steps: u8,
+}
+
+/// The keep set with the highest expected score among those rerolling at
+/// most `max_rerolls` tiles, as reroll advice. With more than one reroll
+/// left, every one of those keep sets is valued by a plan over the remaining
+/// rerolls, each later step changing up to `HORIZON_REROLLS` tiles.
+pub fn best_keep_advice(
+    inputs: &RerollInputs,
+    rerolls_remaining: u8,
+    max_rerolls: usize,
+) -> Option<RerollAdvice> {
+    let mut scorer = RackScorer::new(inputs);
+    let evaluations = scorer.evaluate_keeps(rerolls_remaining, max_rerolls);
+    let best = evaluations.first()?;
+    let letters = scorer.letters;
+    let current = scorer.best(&letters);
+
+    let mut notes = Vec::new();
+    if best.reroll.is_empty() && best.steps == 1 {
+        notes.push(format!(
+            "Keep the rack: no reroll of up to {max_rerolls} tiles beats {current} on average"
+        ));
+    } else if best.reroll.is_empty() {
+        notes.push(format!(
+            "Hold the rack for now: later rerolls average {:.1} against {current} if you stand",
+            best.expected_score
+        ));
+    } else {
+        notes.push(format!(
+            "Rerolling {} averages {:.1} against {current} if you stand",
+            best.reroll.iter().collect::<String>(),
+            best.expected_score
+        ));
+    }
+    if best.steps > 1 {
+        notes.push(format!(
+            "Planned over {} rerolls, changing up to {HORIZON_REROLLS} tile per later step",
+            best.steps
+        ));
+    }
+
+    Some(RerollAdvice {
+        target_word: "Best expected value".to_string(),
+        missing_letters: Vec::new(),
+        reroll_letters: best.reroll.clone(),
+        keep_letters: best.keep.clone(),
+        held_tiles: scorer.held,
+        estimated_score: Some(best.expected_score.round() as u32),
+        success_probability: (!best.reroll.is_empty()).then_some(best.improve_probability),
+        phase: "expected_value",
+        notes,
+        focus_tags: vec!["expected-value".to_string()],
+    })
+}
+
+fn sort_evaluations(evaluations: &mut [KeepEvaluation]) {
+    evaluations.sort_by(|a, b| {
+        b.expected_score
+            .total_cmp(&a.expected_score)
+            .then_with(|| a.reroll.len().cmp(&b.reroll.len()))
+            .then_with(|| a.keep.cmp(&b.keep))
+    });
+}
+
+/// Best-word scores of hypothetical racks, memoized by letter counts since
+/// many keep sets and draws end on the same rack.
+pub struct RackScorer<'a> {
+    inputs: &'a RerollInputs<'a>,
+    /// Single letters on the current rack.
+    pub letters: [u8; 26],
+    /// Blanks and multi-letter tiles, which every hypothetical rack keeps.
+    pub held: Vec<String>,
+    memo: HashMap<[u8; 26], u32>,
+    /// Expected final scores by rerolls left and rack.
+    values: HashMap<(u8, [u8; 26]), f64>,
+}
+
+impl<'a> RackScorer<'a> {
+    pub fn new(inputs: &'a RerollInputs<'a>) -> Self {
+        let mut letters = [0u8; 26];
+        let mut held = Vec::new();
+        for tile in inputs.tiles {
+            if tile.len() == 1 && tile.chars().all(|ch| ch.is_ascii_uppercase()) {
+                letters[letter_index(tile)] += 1;
+            } else {
+                held.push(tile.clone());
+            }
+        }
+        Self {
+            inputs,
+            letters,
+            held,
+            memo: HashMap::new(),
+            values: HashMap::new(),
+        }
+    }
+
+    /// Scores every way to keep part of the rack by the expected final score
+    /// after rerolling the rest, best first. Keep sets reroll at most
+    /// `max_rerolls` letters; with more than one reroll left each outcome is
+    /// valued by the best play from there on, changing up to
+    /// `HORIZON_REROLLS` letters per later step. Blanks and multi-letter tiles
+    /// are always kept; keeping everything is included as the baseline.
+    pub fn evaluate_keeps(
+        &mut self,
+        rerolls_remaining: u8,
+        max_rerolls: usize,
+    ) -> Vec<KeepEvaluation> {
+        let counts = self.letters;
+        let current = self.best(&counts);
+        let steps = rerolls_remaining.max(1);
+        let later_max = max_rerolls.min(HORIZON_REROLLS);
+
+        let mut evaluations: Vec<KeepEvaluation> = keep_sets(&counts, max_rerolls)
+            .into_iter()
+            .map(|keep| {
+                let reroll: [u8; 26] = std::array::from_fn(|idx| counts[idx] - keep[idx]);
+                let (expected_score, improve_probability) =
+                    self.expected(&keep, &reroll, steps - 1, later_max, current);
+                KeepEvaluation {
+                    keep: expand(&keep),
+                    reroll: expand(&reroll),
+                    expected_score,
+                    improve_probability,
+                    steps,
+                }
+            })
+            .collect();
+        sort_evaluations(&mut evaluations);
+        evaluations
+    }
+
+    /// Best score of the held tiles plus `counts` letters, 0 if nothing fits.
+    pub fn best(&mut self, counts: &[u8; 26]) -> u32 {
+        if let Some(&score) = self.memo.get(counts) {
+            return score;
+        }
+        let tiles: Vec<String> = self
+            .held
+            .iter()
+            .cloned()
+            .chain(expand(counts).into_iter().map(String::from))
+            .collect();
+        let score = solver::best_score(
+            &tiles,
+            self.inputs.target_length,
+            self.inputs.invalid,
+            self.inputs.scoring,
+        )
+        .unwrap_or(0);
+        self.memo.insert(*counts, score);
+        score
+    }
+
+    /// Expected final score of `counts` with `rerolls` left, taking the best
+    /// keep set at every step and standing when no reroll beats the rack.
+    fn value(&mut self, counts: &[u8; 26], rerolls: u8, max_rerolls: usize) -> f64 {
+        let current = self.best(counts);
+        if rerolls == 0 {
+            return f64::from(current);
+        }
+        if let Some(&value) = self.values.get(&(rerolls, *counts)) {
+            return value;
+        }
+        let value = keep_sets(counts, max_rerolls)
+            .into_iter()
+            .filter(|keep| keep != counts)
+            .map(|keep| {
+                let reroll: [u8; 26] = std::array::from_fn(|idx| counts[idx] - keep[idx]);
+                self.expected(&keep, &reroll, rerolls - 1, max_rerolls, current)
+                    .0
+            })
+            .fold(f64::from(current), f64::max);
+        self.values.insert((rerolls, *counts), value);
+        value
+    }
+
+    /// Expected final score, and the chance the next rack beats `current`,
+    /// after replacing `reroll` with a draw from the bag less the kept
+    /// letters and playing `later` more rerolls after it.
+    fn expected(
+        &mut self,
+        keep: &[u8; 26],
+        reroll: &[u8; 26],
+        later: u8,
+        max_rerolls: usize,
+        current: u32,
+    ) -> (f64, f64) {
+        let draws: u32 = reroll.iter().map(|&count| u32::from(count)).sum();
+        if draws == 0 {
+            return (self.value(keep, later, max_rerolls), 0.0);
+        }
+        if draws as usize > MAX_EXACT_REROLLS {
+            return self.sampled(keep, draws as usize, later, max_rerolls, current);
+        }
+        let bag: [u8; 26] =
+            std::array::from_fn(|idx| self.inputs.bag[idx].saturating_sub(keep[idx]));
+        let outcomes = solver::binomial(bag.iter().map(|&count| u32::from(count)).sum(), draws);
+        if outcomes == 0.0 {
+            return (f64::from(current), 0.0);
+        }
+
+        let mut expected = 0.0;
+        let mut improve = 0.0;
+        for (rack, ways) in draw_outcomes(&bag, draws, keep) {
+            if self.best(&rack) > current {
+                improve += ways;
+            }
+            expected += ways * self.value(&rack, later, max_rerolls);
+        }
+        (expected / outcomes, improve / outcomes)
+    }
+
+    /// `expected` estimated from a fixed set of sampled draws, for rerolls
+    /// too wide to enumerate.
+    fn sampled(
+        &mut self,
+        keep: &[u8; 26],
+        draws: usize,
+        later: u8,
+        max_rerolls: usize,
+        current: u32,
+    ) -> (f64, f64) {
+        let Some(racks) =
+            simulator::sample_draws(self.inputs.bag, keep, draws, SAMPLED_DRAWS, SAMPLE_SEED)
+        else {
+            return (f64::from(current), 0.0);
+        };
+        let mut expected = 0.0;
+        let mut improve = 0.0;
+        for rack in &racks {
+            if self.best(rack) > current {
+                improve += 1.0;
+            }
+            expected += self.value(rack, later, max_rerolls);
+        }
+        let trials = racks.len() as f64;
+        (expected / trials, improve / trials)
+    }
+}
+
+/// Every rack reachable by adding `draws` letters from `bag` to `keep`, with
+/// the number of ways to draw them.
+fn draw_outcomes(bag: &[u8; 26], draws: u32, keep: &[u8; 26]) -> Vec<([u8; 26], f64)> {
+    fn walk(
+        bag: &[u8; 26],
+        idx: usize,
+        remaining: u32,
+        ways: f64,
+        rack: &mut [u8; 26],
+        out: &mut Vec<([u8; 26], f64)>,
+    ) {
+        if remaining == 0 {
+            out.push((*rack, ways));
+            return;
+        }
+        if idx == bag.len() {
+            return;
+        }
+        for take in 0..=remaining.min(u32::from(bag[idx])) {
+            rack[idx] += take as u8;
+            let ways = ways * solver::binomial(u32::from(bag[idx]), take);
+            walk(bag, idx + 1, remaining - take, ways, rack, out);
+            rack[idx] -= take as u8;
+        }
+    }
+
+    let mut out = Vec::new();
+    let mut rack = *keep;
+    walk(bag, 0, draws, 1.0, &mut rack, &mut out);
+    out
+}
+
+/// Every sub-multiset of `counts` that rerolls at most `max_rerolls` letters.
+fn keep_sets(counts: &[u8; 26], max_rerolls: usize) -> Vec<[u8; 26]> {
+    fn walk(
+        counts: &[u8; 26],
+        idx: usize,
+        keep: &mut [u8; 26],
+        rerolled: usize,
+        max_rerolls: usize,
+        out: &mut Vec<[u8; 26]>,
+    ) {
+        if idx == counts.len() {
+            out.push(*keep);
+            return;
+        }
+        for count in 0..=counts[idx] {
+            let dropped = usize::from(counts[idx] - count);
+            if rerolled + dropped > max_rerolls {
+                continue;
+            }
+            keep[idx] = count;
+            walk(counts, idx + 1, keep, rerolled + dropped, max_rerolls, out);
+        }
+        keep[idx] = 0;
+    }
+
+    let mut out = Vec::new();
+    walk(counts, 0, &mut [0u8; 26], 0, max_rerolls, &mut out);
+    out
+}
+
+pub fn expand(counts: &[u8; 26]) -> Vec<char> {
+    counts
+        .iter()
+        .enumerate()
+        .flat_map(|(idx, &count)| std::iter::repeat_n((b'A' + idx as u8) as char, count.into()))
+        .collect()
+}
+
+fn letter_index(tile: &str) -> usize {
+    (tile.as_bytes()[0] - b'A') as usize
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scoring::{Bonus, SCRABBLE};
+    use crate::solver::LETTER_BAG_COUNTS;
+
+    #[test]
+    fn rerolls_dead_letters_and_keeps_strong_racks() {
+        let invalid = HashSet::new();
+        let bonuses = [Bonus::None, Bonus::None, Bonus::DoubleWord];
+        let scoring = ScoreContext {
+            profile: &SCRABBLE,
+            bonuses: &bonuses,
+            round: 2,
+            slot_length: None,
+        };
+        let evaluate = |rack: &str, rerolls: u8| {
+            let tiles: Vec<String> = rack.chars().map(String::from).collect();
+            let inputs = RerollInputs {
+                tiles: &tiles,
+                target_length: Some(3),
+                invalid: &invalid,
+                scoring: &scoring,
+                bag: &LETTER_BAG_COUNTS,
+            };
+            (
+                RackScorer::new(&inputs).evaluate_keeps(1, MAX_EXACT_REROLLS),
+                best_keep_advice(&inputs, rerolls, MAX_EXACT_REROLLS).unwrap(),
+            )
+        };
+
+        // VEX and VOX are one draw away; a second V is dead weight.
+        let (keeps, advice) = evaluate("XVV", 1);
+        assert_eq!(keeps.len(), 6);
+        assert_eq!(keeps[0].keep, vec!['V', 'X']);
+        assert_eq!(advice.reroll_letters, vec!['V']);
+        assert_eq!(advice.phase, "expected_value");
+        assert!(advice.estimated_score.unwrap() > 0);
+        let odds = advice.success_probability.unwrap();
+        assert!(odds > 0.0 && odds < 1.0);
+
+        let (keeps, advice) = evaluate("JAM", 1);
+        assert!(keeps[0].reroll.is_empty());
+        assert!(advice.reroll_letters.is_empty());
+        assert_eq!(advice.estimated_score, Some(48));
+        assert_eq!(advice.success_probability, None);
+
+        // A second shot is worth more even when the first reroll is the same.
+        let (_, one_shot) = evaluate("ZKV", 1);
+        let (_, two_shots) = evaluate("ZKV", 2);
+        assert_eq!(one_shot.keep_letters, vec!['K']);
+        assert_eq!(two_shots.keep_letters, vec!['K']);
+        assert!(two_shots.estimated_score > one_shot.estimated_score);
+        assert!(two_shots
+            .notes
+            .iter()
+            .any(|note| note.contains("2 rerolls")));
+    }
+
+    #[test]
+    fn later_rerolls_change_the_first_keep() {
+        let invalid = HashSet::new();
+        let bonuses = [Bonus::TripleLetter];
+        let scoring = ScoreContext {
+            profile: &SCRABBLE,
+            bonuses: &bonuses,
+            round: 1,
+            slot_length: None,
+        };
+        let tiles: Vec<String> = "QXZJVKW".chars().map(String::from).collect();
+        let inputs = RerollInputs {
+            tiles: &tiles,
+            target_length: None,
+            invalid: &invalid,
+            scoring: &scoring,
+            bag: &LETTER_BAG_COUNTS,
+        };
+
+        let one_shot = best_keep_advice(&inputs, 1, 1).unwrap();
+        let two_shots = best_keep_advice(&inputs, 2, 1).unwrap();
+        assert_eq!(one_shot.reroll_letters, vec!['Z']);
+        assert_eq!(two_shots.reroll_letters, vec!['V']);
+        assert!(two_shots.estimated_score > one_shot.estimated_score);
+    }
+}
@@ -0,0 +1,252 @@
+use std::collections::HashSet;
+
+use crate::game::ROUNDS;
+use crate::history::{GameRecord, SlotRecord};
+use crate::scoring::{Bonus, ScoreContext, ScoringProfile};
+use crate::solver::{self, ScoreBreakdown};
+
+/// Why a played word fell short of the best one, checked in this order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mistake {
+    /// The best word covered a DW or TW square the played word left empty.
+    IgnoredDw,
+    /// The best word put more letter value on the TL squares.
+    MissedTlHitter,
+    /// The best word was longer or shorter.
+    WrongLength,
+    /// Same length and squares, just a lower-scoring word.
+    WeakerWord,
+}
+
+impl Mistake {
+    pub const ALL: [Mistake; 4] = [
+        Mistake::IgnoredDw,
+        Mistake::MissedTlHitter,
+        Mistake::WrongLength,
+        Mistake::WeakerWord,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Mistake::IgnoredDw => "ignored_dw",
+            Mistake::MissedTlHitter => "missed_tl_hitter",
+            Mistake::WrongLength => "wrong_length",
+            Mistake::WeakerWord => "weaker_word",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SlotReview {
+    pub round: u8,
+    pub slot_index: u8,
+    pub rack: Vec<String>,
+    pub played_word: String,
+    pub played_score: u32,
+    /// Best word the solver finds on the same rack, bonuses and round.
+    pub optimal: Option<(String, ScoreBreakdown)>,
+    /// Points between the best word and the played one, both on their best
+    /// squares of the slot.
+    pub lost: u32,
+    pub mistake: Option<Mistake>,
+}
+
+#[derive(Debug, Clone)]
+pub struct GameReview {
+    pub slots: Vec<SlotReview>,
+    /// Points lost in each round, 1 to `ROUNDS`.
+    pub lost_by_round: Vec<(u8, u32)>,
+    /// Slots and points lost per kind of mistake.
+    pub lost_by_mistake: Vec<(Mistake, u32, u32)>,
+    pub lost: u32,
+}
+
+/// Replays every played slot of a logged game through the solver with the
+/// rack the word was played from, its bonuses and its round. Slots without a
+/// recorded rack can't be replayed and are left out.
+pub fn review_game(
+    record: &GameRecord,
+    profile: &'static ScoringProfile,
+    invalid: &HashSet<String>,
+) -> GameReview {
+    summarize(
+        record
+            .slots
+            .iter()
+            .filter_map(|slot| review_slot(slot, profile, invalid))
+            .collect(),
+    )
+}
+
+fn review_slot(
+    slot: &SlotRecord,
+    profile: &'static ScoringProfile,
+    invalid: &HashSet<String>,
+) -> Option<SlotReview> {
+    let played_word = slot.word.clone()?;
+    let played_score = slot.score?;
+    let rack = slot.racks.last()?.clone();
+    let bonuses: Vec<Bonus> = slot
+        .bonuses
+        .iter()
+        .map(|value| Bonus::from_str_raw(value))
+        .collect();
+    let scoring = ScoreContext {
+        profile,
+        bonuses: &bonuses,
+        round: slot.round,
+        slot_length: slot.slot_length.map(usize::from),
+    };
+    let optimal = solver::solve_rack(&rack, None, invalid, 1, &scoring, None, None)
+        .into_iter()
+        .next();
+    // The recorded score is the one the scorecard kept. The layout behind it
+    // is only needed to name the mistake, so find the square that scores it.
+    let played = (0..scoring.slot_length.unwrap_or(1)).find_map(|offset| {
+        solver::score_on_rack_at(&played_word, &rack, &scoring, Some(offset))
+            .filter(|breakdown| breakdown.total == played_score)
+    });
+    let lost = optimal
+        .as_ref()
+        .map_or(0, |best| best.score.saturating_sub(played_score));
+    let mistake = match &optimal {
+        Some(best) if lost > 0 => Some(classify(
+            &played_word,
+            played.as_ref(),
+            best.word.as_str(),
+            &best.breakdown,
+        )),
+        _ => None,
+    };
+    Some(SlotReview {
+        round: slot.round,
+        slot_index: slot.slot_index,
+        rack,
+        played_word,
+        played_score,
+        optimal: optimal.map(|best| (best.word, best.breakdown)),
+        lost,
+        mistake,
+    })
+}
+
+fn classify(
+    played_word: &str,
+    played: Option<&ScoreBreakdown>,
+    best_word: &str,
+    best: &ScoreBreakdown,
+) -> Mistake {
+    let word_bonus = |breakdown: &ScoreBreakdown| breakdown.word_multiplier > 1;
+    let tl_value = |breakdown: &ScoreBreakdown| -> u32 {
+        breakdown
+            .tiles
+            .iter()
+            .filter(|tile| tile.bonus == Bonus::TripleLetter && !tile.blank)
+            .map(|tile| tile.base)
+            .sum()
+    };
+    if word_bonus(best) && !played.is_some_and(word_bonus) {
+        Mistake::IgnoredDw
+    } else if tl_value(best) > played.map_or(0, tl_value) {
+        Mistake::MissedTlHitter
+    } else if best_word.chars().count() != played_word.chars().count() {
+        Mistake::WrongLength
+    } else {
+        Mistake::WeakerWord
+    }
+}
+
+/// Totals of `slots` by round and by kind of mistake.
+fn summarize(slots: Vec<SlotReview>) -> GameReview {
+    let lost_by_round = (1..=ROUNDS as u8)
+        .map(|round| {
+            let lost = slots
+                .iter()
+                .filter(|slot| slot.round == round)
+                .map(|slot| slot.lost)
+                .sum();
+            (round, lost)
+        })
+        .collect();
+    let lost_by_mistake = Mistake::ALL
+        .iter()
+        .map(|&mistake| {
+            let matching = slots.iter().filter(|slot| slot.mistake == Some(mistake));
+            let count = matching.clone().count() as u32;
+            (mistake, count, matching.map(|slot| slot.lost).sum())
+        })
+        .collect();
+    let lost = slots.iter().map(|slot| slot.lost).sum();
+    GameReview {
+        slots,
+        lost_by_round,
+        lost_by_mistake,
+        lost,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scoring::SCRABBLE;
+
+    fn slot(word: &str, score: u32, rack: &str, bonuses: &[&str]) -> SlotRecord {
+        SlotRecord {
+            round: 1,
+            slot_index: 0,
+            bonuses: bonuses.iter().map(|code| code.to_string()).collect(),
+            slot_length: None,
+            racks: vec![rack.chars().map(String::from).collect()],
+            word: Some(word.to_string()),
+            score: Some(score),
+            skipped: false,
+        }
+    }
+
+    #[test]
+    fn missed_points_are_sorted_by_mistake() {
+        let invalid = HashSet::new();
+        let review = |slot: &SlotRecord| review_slot(slot, &SCRABBLE, &invalid).unwrap();
+
+        // TA puts the T on the TL for 4; JAM would have put J(8) there for 28.
+        let tl = review(&slot("TA", 4, "JAMT", &["TL"]));
+        assert_eq!(tl.optimal.as_ref().unwrap().0, "JAM");
+        assert_eq!(tl.lost, 24);
+        assert_eq!(tl.mistake, Some(Mistake::MissedTlHitter));
+
+        // Slid onto the TL, AX can't also reach the DW; SAX covers both.
+        let mut dw = slot("AX", 25, "AXES", &["DW", "NONE", "TL"]);
+        dw.slot_length = Some(3);
+        let dw = review(&dw);
+        assert_eq!(dw.optimal.as_ref().unwrap().0, "SAX");
+        assert_eq!(dw.lost, 27);
+        assert_eq!(dw.mistake, Some(Mistake::IgnoredDw));
+
+        // AT slid onto the DW for 4 just like ACT does, so only the length
+        // cost points.
+        let mut short = slot("AT", 4, "CAT", &["NONE", "NONE", "DW"]);
+        short.slot_length = Some(3);
+        let short = review(&short);
+        assert_eq!(short.lost, 6);
+        assert_eq!(short.mistake, Some(Mistake::WrongLength));
+
+        // Pinned to the first two squares, the same AT scored 2 and missed
+        // the DW; the loss is counted from the score actually recorded.
+        let mut pinned = slot("AT", 2, "CAT", &["NONE", "NONE", "DW"]);
+        pinned.slot_length = Some(3);
+        let pinned = review(&pinned);
+        assert_eq!(pinned.played_score + pinned.lost, 10);
+        assert_eq!(pinned.mistake, Some(Mistake::IgnoredDw));
+
+        let best = review(&slot("JAM", 28, "JAMT", &["TL"]));
+        assert_eq!(best.lost, 0);
+        assert_eq!(best.mistake, None);
+
+        let totals = summarize(vec![tl, dw, short, best]);
+        assert_eq!(totals.lost, 24 + 27 + 6);
+        assert_eq!(totals.lost_by_round[0].1, totals.lost);
+        assert_eq!(totals.lost_by_mistake[0], (Mistake::IgnoredDw, 1, 27));
+        assert_eq!(totals.lost_by_mistake[1], (Mistake::MissedTlHitter, 1, 24));
+        assert_eq!(totals.lost_by_mistake[2], (Mistake::WrongLength, 1, 6));
+    }
+}
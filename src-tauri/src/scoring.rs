@@ -1,12 +1,182 @@
-pub fn letter_value(ch: char) -> Option<u8> {
-    match ch.to_ascii_uppercase() {
-        'R' | 'A' | 'E' | 'I' | 'S' | 'T' | 'O' => Some(1),
-        'L' | 'U' | 'D' | 'N' => Some(2),
-        'H' | 'G' | 'Y' => Some(3),
-        'C' | 'B' | 'F' | 'P' | 'W' | 'M' => Some(4),
-        'K' | 'V' => Some(5),
-        'X' => Some(8),
-        'Q' | 'Z' | 'J' => Some(10),
-        _ => None,
+use std::ops::Range;
+
+/// Rack entry for a wildcard tile; it can stand for any letter and scores 0.
+pub const BLANK: char = '?';
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Bonus {
+    None,
+    DoubleLetter,
+    TripleLetter,
+    DoubleWord,
+    TripleWord,
+}
+
+impl Bonus {
+    pub fn from_str_raw(value: &str) -> Self {
+        match value.trim().to_ascii_uppercase().as_str() {
+            "DL" => Bonus::DoubleLetter,
+            "TL" => Bonus::TripleLetter,
+            "DW" => Bonus::DoubleWord,
+            "TW" => Bonus::TripleWord,
+            _ => Bonus::None,
+        }
+    }
+
+    pub fn as_code(self) -> &'static str {
+        match self {
+            Bonus::None => "NONE",
+            Bonus::DoubleLetter => "DL",
+            Bonus::TripleLetter => "TL",
+            Bonus::DoubleWord => "DW",
+            Bonus::TripleWord => "TW",
+        }
+    }
+}
+
+/// Letter values and multipliers used to score a word. Requests pick one of
+/// the presets by name.
+#[derive(Debug, Clone)]
+pub struct ScoringProfile {
+    pub name: &'static str,
+    letter_values: [u8; 26],
+    double_letter: u32,
+    triple_letter: u32,
+    double_word: u32,
+    triple_word: u32,
+    round_multipliers: [u32; 5],
+}
+
+/// Letter values from `YatzyRules.md` (standard Scrabble points).
+pub static SCRABBLE: ScoringProfile = ScoringProfile {
+    name: "scrabble",
+    // A  B  C  D  E  F  G  H  I  J  K  L  M  N  O  P  Q   R  S  T  U  V  W  X  Y  Z
+    letter_values: [
+        1, 3, 3, 2, 1, 4, 2, 4, 1, 8, 5, 1, 3, 1, 1, 3, 10, 1, 1, 1, 1, 4, 4, 8, 4, 10,
+    ],
+    double_letter: 2,
+    triple_letter: 3,
+    double_word: 2,
+    triple_word: 3,
+    round_multipliers: [1, 2, 3, 4, 5],
+};
+
+/// The table the coach shipped with before profiles existed.
+pub static LEGACY: ScoringProfile = ScoringProfile {
+    name: "legacy",
+    // A  B  C  D  E  F  G  H  I  J   K  L  M  N  O  P  Q   R  S  T  U  V  W  X  Y  Z
+    letter_values: [
+        1, 4, 4, 2, 1, 4, 3, 3, 1, 10, 5, 2, 4, 2, 1, 4, 10, 1, 1, 1, 2, 5, 4, 8, 3, 10,
+    ],
+    double_letter: 2,
+    triple_letter: 3,
+    double_word: 2,
+    triple_word: 3,
+    round_multipliers: [1, 2, 3, 4, 5],
+};
+
+pub const DEFAULT_PROFILE: &ScoringProfile = &SCRABBLE;
+
+pub const PROFILES: [&ScoringProfile; 2] = [&SCRABBLE, &LEGACY];
+
+impl ScoringProfile {
+    pub fn by_name(name: &str) -> Option<&'static ScoringProfile> {
+        let name = name.trim();
+        PROFILES
+            .into_iter()
+            .find(|profile| profile.name.eq_ignore_ascii_case(name))
+    }
+
+    pub fn letter_value(&self, ch: char) -> Option<u8> {
+        let ch = ch.to_ascii_uppercase();
+        if !ch.is_ascii_uppercase() {
+            return None;
+        }
+        Some(self.letter_values[(ch as u8 - b'A') as usize])
+    }
+
+    pub fn letter_multiplier(&self, bonus: Bonus) -> u32 {
+        match bonus {
+            Bonus::DoubleLetter => self.double_letter,
+            Bonus::TripleLetter => self.triple_letter,
+            _ => 1,
+        }
+    }
+
+    pub fn word_multiplier(&self, bonus: Bonus) -> u32 {
+        match bonus {
+            Bonus::DoubleWord => self.double_word,
+            Bonus::TripleWord => self.triple_word,
+            _ => 1,
+        }
+    }
+
+    /// Average value of a tile drawn from `bag`.
+    pub fn mean_letter_value(&self, bag: &[u8; 26]) -> f64 {
+        let (weighted, total) = bag.iter().zip(self.letter_values).fold(
+            (0.0, 0.0),
+            |(weighted, total), (&count, value)| {
+                (
+                    weighted + f64::from(count) * f64::from(value),
+                    total + f64::from(count),
+                )
+            },
+        );
+        if total == 0.0 {
+            0.0
+        } else {
+            weighted / total
+        }
+    }
+
+    /// Multiplier for a 1-based round; rounds past the table reuse the last entry.
+    pub fn round_multiplier(&self, round: u8) -> u32 {
+        let idx = usize::from(round.max(1) - 1).min(self.round_multipliers.len() - 1);
+        self.round_multipliers[idx]
+    }
+}
+
+/// Everything needed to score a word on one slot: the profile, the slot's
+/// bonus squares and the round being played.
+#[derive(Debug, Clone, Copy)]
+pub struct ScoreContext<'a> {
+    pub profile: &'a ScoringProfile,
+    pub bonuses: &'a [Bonus],
+    pub round: u8,
+    /// Squares in the slot. When set, a shorter word may start on any square
+    /// that keeps it inside the slot; otherwise words start on square 0.
+    pub slot_length: Option<usize>,
+}
+
+impl ScoreContext<'_> {
+    pub fn round_multiplier(&self) -> u32 {
+        self.profile.round_multiplier(self.round)
+    }
+
+    /// Squares a word of `tile_count` tiles may start on.
+    pub fn offsets(&self, tile_count: usize) -> Range<usize> {
+        match self.slot_length {
+            Some(len) if tile_count <= len => 0..len - tile_count + 1,
+            Some(_) => 0..0,
+            None => 0..1,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn presets_match_their_tables() {
+        assert_eq!(SCRABBLE.letter_value('l'), Some(1));
+        assert_eq!(SCRABBLE.letter_value('M'), Some(3));
+        assert_eq!(SCRABBLE.letter_value('J'), Some(8));
+        assert_eq!(LEGACY.letter_value('L'), Some(2));
+        assert_eq!(LEGACY.letter_value('J'), Some(10));
+        assert_eq!(SCRABBLE.letter_value('-'), None);
+        assert_eq!(SCRABBLE.round_multiplier(5), 5);
+        assert!(ScoringProfile::by_name(" Legacy ").is_some());
+        assert!(ScoringProfile::by_name("wordfeud").is_none());
     }
 }
@@ -0,0 +1,383 @@
+use crate::game::{Placement, Scorecard, ROUNDS, SLOTS_PER_ROUND};
+use crate::planner::SlotLayout;
+use crate::scoring::ScoringProfile;
+
+/// What happened to one slot of the current round.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SlotStatus {
+    Open,
+    Played,
+    Skipped,
+}
+
+/// One change to a game session. Sessions keep every command they ran so
+/// they can be undone and redone.
+#[derive(Debug, Clone)]
+pub enum SessionCommand {
+    /// A fresh rack, or the whole rack after a reroll.
+    Draw {
+        tiles: Vec<String>,
+    },
+    Play {
+        slot: usize,
+        word: String,
+        /// 0-based square the word starts on; the best-scoring one when unset.
+        slot_offset: Option<usize>,
+    },
+    Skip {
+        slot: usize,
+    },
+    /// Next round with these layouts.
+    Advance {
+        layouts: Vec<SlotLayout>,
+    },
+    Finish,
+}
+
+/// A match in progress: the round being played, its slot layouts, and every
+/// word played so far on a scorecard.
+#[derive(Debug, Clone)]
+pub struct GameSession {
+    player: String,
+    profile: &'static ScoringProfile,
+    board: Board,
+    done: Vec<SessionCommand>,
+    undone: Vec<SessionCommand>,
+}
+
+impl GameSession {
+    pub fn start(player: &str, profile: &'static ScoringProfile, layouts: Vec<SlotLayout>) -> Self {
+        Self {
+            player: player.trim().to_string(),
+            profile,
+            board: Board::new(profile, layouts),
+            done: Vec::new(),
+            undone: Vec::new(),
+        }
+    }
+
+    pub fn player(&self) -> &str {
+        &self.player
+    }
+
+    pub fn round(&self) -> u8 {
+        self.board.round
+    }
+
+    pub fn card(&self) -> &Scorecard {
+        &self.board.card
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.board.finished
+    }
+
+    /// Layouts of a 1-based round that has been started.
+    pub fn layouts(&self, round: u8) -> &[SlotLayout] {
+        self.board.layouts(round)
+    }
+
+    pub fn status(&self, round: u8, slot: usize) -> SlotStatus {
+        self.board.status(round, slot)
+    }
+
+    /// Racks drawn for the word played in a slot: the fresh rack, then the
+    /// rack after each reroll.
+    pub fn draws(&self, round: u8, slot: usize) -> &[Vec<String>] {
+        &self.board.draws[usize::from(round.max(1) - 1)][slot]
+    }
+
+    /// The rack as last drawn, if anything has been drawn since the last play.
+    pub fn rack(&self) -> Option<&[String]> {
+        self.board.turn.last().map(Vec::as_slice)
+    }
+
+    /// First slot of the current round nothing has been played into or skipped.
+    pub fn next_open_slot(&self) -> Option<usize> {
+        (0..SLOTS_PER_ROUND).find(|&slot| self.status(self.board.round, slot) == SlotStatus::Open)
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.done.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.undone.is_empty()
+    }
+
+    /// Records a fresh rack, or the rack after a reroll, for the next play.
+    pub fn draw(&mut self, tiles: Vec<String>) -> Result<(), String> {
+        self.run(SessionCommand::Draw { tiles })
+    }
+
+    /// Scores `word` on the slot's bonuses in the current round, played
+    /// from the rack last drawn. `slot_offset` pins the 0-based square it
+    /// starts on; without it the best-scoring square is used.
+    pub fn play(
+        &mut self,
+        slot: usize,
+        word: &str,
+        slot_offset: Option<usize>,
+    ) -> Result<u32, String> {
+        self.run(SessionCommand::Play {
+            slot,
+            word: word.to_string(),
+            slot_offset,
+        })?;
+        Ok(self
+            .board
+            .card
+            .slot(self.board.round, slot)
+            .map_or(0, |played| played.score))
+    }
+
+    /// Leaves a slot of the current round empty for good.
+    pub fn skip(&mut self, slot: usize) -> Result<(), String> {
+        self.run(SessionCommand::Skip { slot })
+    }
+
+    /// Moves to the next round, with new slot layouts or the current ones again.
+    pub fn advance(&mut self, layouts: Option<Vec<SlotLayout>>) -> Result<u8, String> {
+        let layouts = layouts.unwrap_or_else(|| self.layouts(self.board.round).to_vec());
+        self.run(SessionCommand::Advance { layouts })?;
+        Ok(self.board.round)
+    }
+
+    /// Ends the game and drops its command history, so a finished game
+    /// can't be undone or redone into a second log entry.
+    pub fn finish(&mut self) -> Result<u32, String> {
+        self.board.apply(&SessionCommand::Finish)?;
+        self.done.clear();
+        self.undone.clear();
+        Ok(self.board.card.total())
+    }
+
+    /// Reverts the last command by replaying everything before it from the
+    /// opening layouts, so every score is recomputed.
+    pub fn undo(&mut self) -> Result<(), String> {
+        self.ensure_open()?;
+        let command = self.done.pop().ok_or("Nothing to undo.")?;
+        self.undone.push(command);
+        let mut board = Board::new(self.profile, self.board.layouts[0].clone());
+        for command in &self.done {
+            board.apply(command)?;
+        }
+        self.board = board;
+        Ok(())
+    }
+
+    pub fn redo(&mut self) -> Result<(), String> {
+        self.ensure_open()?;
+        let command = self.undone.last().ok_or("Nothing to redo.")?;
+        self.board.apply(command)?;
+        let command = self.undone.pop().expect("checked above");
+        self.done.push(command);
+        Ok(())
+    }
+
+    fn ensure_open(&self) -> Result<(), String> {
+        if self.is_finished() {
+            return Err("The game is over; start a new one.".into());
+        }
+        Ok(())
+    }
+
+    /// Applies a new command; anything undone before it can no longer be redone.
+    fn run(&mut self, command: SessionCommand) -> Result<(), String> {
+        self.board.apply(&command)?;
+        self.done.push(command);
+        self.undone.clear();
+        Ok(())
+    }
+}
+
+/// Session state rebuilt from its commands.
+#[derive(Debug, Clone)]
+struct Board {
+    card: Scorecard,
+    round: u8,
+    /// Slot layouts of every round started so far.
+    layouts: Vec<Vec<SlotLayout>>,
+    skipped: [[bool; SLOTS_PER_ROUND]; ROUNDS],
+    /// Racks drawn since the last play.
+    turn: Vec<Vec<String>>,
+    draws: [[Vec<Vec<String>>; SLOTS_PER_ROUND]; ROUNDS],
+    finished: bool,
+}
+
+impl Board {
+    fn new(profile: &'static ScoringProfile, layouts: Vec<SlotLayout>) -> Self {
+        Self {
+            card: Scorecard::new(profile),
+            round: 1,
+            layouts: vec![layouts],
+            skipped: Default::default(),
+            turn: Vec::new(),
+            draws: Default::default(),
+            finished: false,
+        }
+    }
+
+    fn layouts(&self, round: u8) -> &[SlotLayout] {
+        self.layouts
+            .get(usize::from(round.max(1) - 1))
+            .map_or(&[], Vec::as_slice)
+    }
+
+    fn status(&self, round: u8, slot: usize) -> SlotStatus {
+        if self.card.slot(round, slot).is_some() {
+            SlotStatus::Played
+        } else if self.skipped[usize::from(round.max(1) - 1)][slot] {
+            SlotStatus::Skipped
+        } else {
+            SlotStatus::Open
+        }
+    }
+
+    fn apply(&mut self, command: &SessionCommand) -> Result<(), String> {
+        if self.finished {
+            return Err("The game is over; start a new one.".into());
+        }
+        match command {
+            SessionCommand::Play {
+                slot,
+                word,
+                slot_offset,
+            } => {
+                let layout = self.open_slot(*slot)?.clone();
+                let placement = Placement {
+                    bonuses: &layout.bonuses,
+                    slot_length: layout.slot_length,
+                    slot_offset: *slot_offset,
+                    rack: self.turn.last().map(Vec::as_slice),
+                };
+                self.card.play(self.round, *slot, word, &placement)?;
+                self.draws[usize::from(self.round - 1)][*slot] = std::mem::take(&mut self.turn);
+            }
+            SessionCommand::Draw { tiles } => {
+                if tiles.is_empty() {
+                    return Err("Add the rack letters that were drawn.".into());
+                }
+                self.turn.push(tiles.clone());
+            }
+            SessionCommand::Skip { slot } => {
+                self.open_slot(*slot)?;
+                self.skipped[usize::from(self.round - 1)][*slot] = true;
+            }
+            SessionCommand::Advance { layouts } => {
+                if usize::from(self.round) >= ROUNDS {
+                    return Err("This is the last round; finish the game instead.".into());
+                }
+                self.layouts.push(layouts.clone());
+                self.round += 1;
+                // A rack drawn but not played doesn't carry into the next round.
+                self.turn.clear();
+            }
+            SessionCommand::Finish => self.finished = true,
+        }
+        Ok(())
+    }
+
+    fn open_slot(&self, slot: usize) -> Result<&SlotLayout, String> {
+        let layout = self
+            .layouts(self.round)
+            .get(slot)
+            .ok_or_else(|| format!("Slot index must be between 0 and {}.", SLOTS_PER_ROUND - 1))?;
+        match self.status(self.round, slot) {
+            SlotStatus::Open => Ok(layout),
+            SlotStatus::Played => Err(format!("Slot {slot} already has a word this round.")),
+            SlotStatus::Skipped => Err(format!("Slot {slot} was skipped this round.")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scoring::{Bonus, SCRABBLE};
+
+    #[test]
+    fn session_tracks_slots_rounds_and_score() {
+        let mut layouts = vec![SlotLayout::default(); SLOTS_PER_ROUND];
+        layouts[0] = SlotLayout {
+            bonuses: vec![Bonus::TripleLetter],
+            slot_length: Some(3),
+        };
+        layouts[2].slot_length = Some(4);
+        let mut session = GameSession::start("Ada", &SCRABBLE, layouts);
+
+        session
+            .draw(vec!["Q".into(), "V".into(), "T".into()])
+            .unwrap();
+        session
+            .draw(vec!["Q".into(), "A".into(), "T".into()])
+            .unwrap();
+        // Q(10)×TL + A + T = 32 in round 1.
+        assert_eq!(session.play(0, "qat", None).unwrap(), 32);
+        assert_eq!(session.draws(1, 0).len(), 2);
+        assert!(session.rack().is_none());
+        assert!(session.play(0, "AT", None).is_err());
+        assert!(session.play(2, "QUART", None).is_err());
+        session.skip(1).unwrap();
+        assert_eq!(session.status(1, 1), SlotStatus::Skipped);
+        assert_eq!(session.next_open_slot(), Some(2));
+
+        session.draw(vec!["Z".into()]).unwrap();
+        assert_eq!(session.advance(None).unwrap(), 2);
+        assert!(session.rack().is_none());
+        assert_eq!(session.layouts(2)[0].bonuses, vec![Bonus::TripleLetter]);
+        // Same word, round 2 multiplier.
+        assert_eq!(session.play(0, "QAT", None).unwrap(), 64);
+        assert_eq!(session.card().total(), 96);
+
+        // Plays come from the rack: the blank scores nothing, the QU tile
+        // fills one square, and letters missing from the rack are refused.
+        session
+            .draw(vec!["QU".into(), "I".into(), "?".into()])
+            .unwrap();
+        assert!(session.play(2, "QUILT", None).is_err());
+        assert_eq!(session.play(2, "QUIT", Some(0)).unwrap(), 24);
+        session.undo().unwrap();
+        assert!(session.play(2, "QUIT", Some(2)).is_err());
+        session.undo().unwrap();
+        assert_eq!(session.card().total(), 96);
+
+        for _ in 2..ROUNDS {
+            session.advance(None).unwrap();
+        }
+        assert!(session.advance(None).is_err());
+
+        // Back to round 2, where the QAT can be taken back and replayed.
+        for _ in 0..ROUNDS - 2 {
+            session.undo().unwrap();
+        }
+        assert_eq!(session.round(), 2);
+        session.undo().unwrap();
+        assert_eq!(session.card().total(), 32);
+        assert_eq!(session.status(2, 0), SlotStatus::Open);
+        session.redo().unwrap();
+        assert_eq!(session.card().total(), 96);
+
+        // A new command drops the redo stack.
+        session.skip(3).unwrap();
+        assert!(!session.can_redo());
+        assert!(session.redo().is_err());
+
+        // Finishing locks the game for good.
+        assert_eq!(session.finish().unwrap(), 96);
+        assert!(session.skip(2).is_err());
+        assert!(!session.can_undo() && !session.can_redo());
+        assert!(session.undo().is_err());
+        assert!(session.finish().is_err());
+
+        let mut session = GameSession::start("Ada", &SCRABBLE, session.layouts(1).to_vec());
+        session.play(0, "QAT", None).unwrap();
+        session.skip(1).unwrap();
+        while session.can_undo() {
+            session.undo().unwrap();
+        }
+        assert_eq!(session.card().total(), 0);
+        assert_eq!(session.next_open_slot(), Some(0));
+        assert!(session.undo().is_err());
+    }
+}
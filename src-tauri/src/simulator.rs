@@ -0,0 +1,150 @@
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+use crate::reroll_ev::{self, RackScorer, RerollInputs};
+
+pub const DEFAULT_TRIALS: u32 = 2_000;
+pub const MAX_TRIALS: u32 = 50_000;
+/// Percentiles reported for every simulation.
+pub const PERCENTILES: [u8; 5] = [10, 25, 50, 75, 90];
+
+#[derive(Debug, Clone)]
+pub struct SimulationReport {
+    pub keep: Vec<char>,
+    pub reroll: Vec<char>,
+    pub trials: u32,
+    pub seed: u32,
+    /// Best score on the rack before rerolling.
+    pub current_best: u32,
+    pub mean: f64,
+    pub percentiles: Vec<(u8, u32)>,
+    /// Share of trials whose best word beat `current_best`.
+    pub beat_probability: f64,
+}
+
+/// Rerolls everything but `keep` `trials` times, drawing without replacement
+/// from the bag less the kept letters, and records the best score of each
+/// new rack. The same seed always replays the same draws.
+pub fn simulate_reroll(
+    inputs: &RerollInputs,
+    keep: &[char],
+    trials: u32,
+    seed: u32,
+) -> Result<SimulationReport, String> {
+    let mut scorer = RackScorer::new(inputs);
+    let letters = scorer.letters;
+    let mut kept = [0u8; 26];
+    for &ch in keep {
+        let idx = (ch as u8).wrapping_sub(b'A') as usize;
+        if idx >= 26 || kept[idx] >= letters[idx] {
+            return Err(format!("'{ch}' is not on the rack to keep."));
+        }
+        kept[idx] += 1;
+    }
+    let reroll: [u8; 26] = std::array::from_fn(|idx| letters[idx] - kept[idx]);
+    let draws: usize = reroll.iter().map(|&count| usize::from(count)).sum();
+
+    let current_best = scorer.best(&letters);
+    let trials = trials.clamp(1, MAX_TRIALS);
+    let mut scores: Vec<u32> = sample_draws(inputs.bag, &kept, draws, trials, seed)
+        .ok_or("Not enough tiles left in the bag to reroll that many.")?
+        .iter()
+        .map(|rack| scorer.best(rack))
+        .collect();
+    scores.sort_unstable();
+
+    let beats = scores.iter().filter(|&&score| score > current_best).count();
+    let mean = scores.iter().map(|&score| f64::from(score)).sum::<f64>() / f64::from(trials);
+    let percentiles = PERCENTILES
+        .iter()
+        .map(|&pct| {
+            let rank = (usize::from(pct) * scores.len()).div_ceil(100).max(1);
+            (pct, scores[rank - 1])
+        })
+        .collect();
+
+    Ok(SimulationReport {
+        keep: reroll_ev::expand(&kept),
+        reroll: reroll_ev::expand(&reroll),
+        trials,
+        seed,
+        current_best,
+        mean,
+        percentiles,
+        beat_probability: beats as f64 / f64::from(trials),
+    })
+}
+
+/// `trials` racks made of `kept` plus `draws` letters drawn without
+/// replacement from `bag` less the kept letters, or `None` when the bag runs
+/// short. The same seed always replays the same draws.
+pub fn sample_draws(
+    bag: &[u8; 26],
+    kept: &[u8; 26],
+    draws: usize,
+    trials: u32,
+    seed: u32,
+) -> Option<Vec<[u8; 26]>> {
+    let pool: Vec<usize> = (0..26)
+        .flat_map(|idx| std::iter::repeat_n(idx, bag[idx].saturating_sub(kept[idx]).into()))
+        .collect();
+    if draws > pool.len() {
+        return None;
+    }
+    let mut rng = ChaCha8Rng::seed_from_u64(u64::from(seed));
+    Some(
+        (0..trials)
+            .map(|_| {
+                let mut rack = *kept;
+                for &idx in pool.choose_multiple(&mut rng, draws) {
+                    rack[idx] += 1;
+                }
+                rack
+            })
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scoring::{ScoreContext, SCRABBLE};
+    use crate::solver::LETTER_BAG_COUNTS;
+    use std::collections::HashSet;
+
+    #[test]
+    fn seeded_runs_replay_and_track_the_exact_odds() {
+        let invalid = HashSet::new();
+        let scoring = ScoreContext {
+            profile: &SCRABBLE,
+            bonuses: &[],
+            round: 1,
+            slot_length: None,
+        };
+        let tiles: Vec<String> = "XVV".chars().map(String::from).collect();
+        let inputs = RerollInputs {
+            tiles: &tiles,
+            target_length: Some(3),
+            invalid: &invalid,
+            scoring: &scoring,
+            bag: &LETTER_BAG_COUNTS,
+        };
+
+        let first = simulate_reroll(&inputs, &['V', 'X'], 4_000, 7).unwrap();
+        let again = simulate_reroll(&inputs, &['X', 'V'], 4_000, 7).unwrap();
+        assert_eq!(first.percentiles, again.percentiles);
+        assert_eq!(first.mean, again.mean);
+        assert_eq!(first.reroll, vec!['V']);
+
+        let exact = reroll_ev::RackScorer::new(&inputs)
+            .evaluate_keeps(1, 1)
+            .into_iter()
+            .find(|keep| keep.keep == ['V', 'X'])
+            .unwrap();
+        assert!((first.mean - exact.expected_score).abs() < 0.5);
+        assert!((first.beat_probability - exact.improve_probability).abs() < 0.03);
+
+        assert!(simulate_reroll(&inputs, &['Q'], 10, 7).is_err());
+    }
+}
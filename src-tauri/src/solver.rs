@@ -1,128 +1,38 @@
 use std::collections::HashSet;
 
-use once_cell::sync::Lazy;
+use crate::acceptance::AcceptanceLog;
+use crate::dictionary;
+use crate::hoarding::Hoard;
+use crate::scoring::{Bonus, ScoreContext, ScoringProfile};
+use crate::tiles::{self, PlacedTile, TileRack};
 
-use crate::scoring;
-
-static CSPELL_WORDS: Lazy<HashSet<String>> = Lazy::new(load_cspell_words);
-static WORD_BLOCKLIST: Lazy<HashSet<String>> = Lazy::new(load_blocklist);
-static CORE_WORDS: Lazy<HashSet<String>> = Lazy::new(load_core_words);
-static DICTIONARY: Lazy<Vec<String>> = Lazy::new(load_dictionary);
-
-const LETTER_BAG_COUNTS: [u8; 26] = [
+pub const LETTER_BAG_COUNTS: [u8; 26] = [
     9, 2, 2, 4, 12, 2, 3, 2, 9, 1, 1, 4, 2, 6, 8, 2, 1, 6, 4, 6, 4, 2, 2, 1, 2, 1,
 ];
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum Bonus {
-    None,
-    DoubleLetter,
-    TripleLetter,
-    DoubleWord,
-    TripleWord,
-}
-
-impl Bonus {
-    pub fn from_str_raw(value: &str) -> Self {
-        match value.trim().to_ascii_uppercase().as_str() {
-            "DL" => Bonus::DoubleLetter,
-            "TL" => Bonus::TripleLetter,
-            "DW" => Bonus::DoubleWord,
-            "TW" => Bonus::TripleWord,
-            _ => Bonus::None,
-        }
-    }
-
-    pub fn as_code(self) -> &'static str {
-        match self {
-            Bonus::None => "NONE",
-            Bonus::DoubleLetter => "DL",
-            Bonus::TripleLetter => "TL",
-            Bonus::DoubleWord => "DW",
-            Bonus::TripleWord => "TW",
-        }
-    }
-
-    fn letter_multiplier(self) -> u32 {
-        match self {
-            Bonus::DoubleLetter => 2,
-            Bonus::TripleLetter => 3,
-            _ => 1,
-        }
-    }
-
-    fn word_multiplier(self) -> u32 {
-        match self {
-            Bonus::DoubleWord => 2,
-            Bonus::TripleWord => 3,
-            _ => 1,
-        }
-    }
-}
-
-fn load_dictionary() -> Vec<String> {
-    let mut words: Vec<String> = include_str!("data/wordlist.txt")
-        .lines()
-        .map(|w| w.trim())
-        .filter(|w| !w.is_empty())
-        .filter(|w| CORE_WORDS.contains(&w.to_ascii_lowercase()))
-        .map(|w| w.to_ascii_uppercase())
-        .filter(|w| w.len() >= 2 && w.len() <= 15 && w.chars().all(|c| c.is_ascii_alphabetic()))
-        .filter(|w| CSPELL_WORDS.contains(w))
-        .filter(|w| contains_vowel(w))
-        .filter(|w| !is_uniform_character(w))
-        .filter(|w| !WORD_BLOCKLIST.contains(w))
-        .collect();
-    words.sort();
-    words.dedup();
-    words
-}
-
-fn load_cspell_words() -> HashSet<String> {
-    include_str!("data/cspell-words.txt")
-        .lines()
-        .map(|w| w.trim())
-        .filter(|w| !w.is_empty())
-        .map(|w| w.to_ascii_uppercase())
-        .collect()
-}
-
-fn load_blocklist() -> HashSet<String> {
-    include_str!("data/invalid-words.txt")
-        .lines()
-        .map(|w| w.trim())
-        .filter(|w| !w.is_empty())
-        .filter(|w| !w.starts_with('#'))
-        .map(|w| w.to_ascii_uppercase())
-        .collect()
-}
-
-fn load_core_words() -> HashSet<String> {
-    include_str!("data/core_words.txt")
-        .lines()
-        .map(|w| w.trim().to_ascii_lowercase())
-        .filter(|w| !w.is_empty())
-        .collect()
-}
-
-fn contains_vowel(word: &str) -> bool {
-    word.chars()
-        .any(|ch| matches!(ch, 'A' | 'E' | 'I' | 'O' | 'U' | 'Y'))
+#[derive(Debug, Clone)]
+pub struct RackCandidate {
+    pub word: String,
+    pub score: u32,
+    pub breakdown: ScoreBreakdown,
+    pub from_allowlist: bool,
+    pub confidence: Option<f64>,
+    /// Value the power letters this word spends could earn in a later slot.
+    pub later_cost: f64,
 }
 
-fn is_uniform_character(word: &str) -> bool {
-    let mut chars = word.chars();
-    if let Some(first) = chars.next() {
-        chars.all(|ch| ch == first)
-    } else {
-        false
+impl RackCandidate {
+    /// Score now less the later value given up to earn it.
+    pub fn net_score(&self) -> f64 {
+        f64::from(self.score) - self.later_cost
     }
 }
 
-#[derive(Debug, Clone)]
-pub struct RackCandidate {
-    pub word: String,
-    pub score: u32,
+/// Acceptance history to score candidates with, and the confidence below
+/// which a word is ranked after every safer play.
+pub struct ConfidenceRanking<'a> {
+    pub log: &'a AcceptanceLog,
+    pub min_confidence: Option<f64>,
 }
 
 #[derive(Debug, Clone)]
@@ -131,6 +41,8 @@ pub struct RerollAdvice {
     pub missing_letters: Vec<char>,
     pub reroll_letters: Vec<char>,
     pub keep_letters: Vec<char>,
+    /// Blanks and multi-letter tiles, which are always kept.
+    pub held_tiles: Vec<String>,
     pub estimated_score: Option<u32>,
     pub success_probability: Option<f64>,
     pub phase: &'static str,
@@ -138,34 +50,71 @@ pub struct RerollAdvice {
     pub focus_tags: Vec<String>,
 }
 
+/// `target_length` counts tiles (squares), so a `QU` tile makes a word one
+/// letter longer than the squares it fills.
 pub fn solve_rack(
-    letters: &[char],
+    tiles: &[String],
     target_length: Option<usize>,
     invalid: &HashSet<String>,
     limit: usize,
-    bonuses: &[Bonus],
-    round_multiplier: u32,
+    scoring: &ScoreContext,
+    ranking: Option<&ConfidenceRanking>,
+    hoard: Option<&Hoard>,
 ) -> Vec<RackCandidate> {
-    if letters.is_empty() {
+    if tiles.is_empty() {
         return Vec::new();
     }
 
-    let rack_counts = letter_counts(letters);
-    let mut candidates: Vec<RackCandidate> = DICTIONARY
-        .iter()
-        .filter(|word| target_length.map_or(true, |len| word.len() == len))
-        .filter(|word| word.len() <= letters.len())
-        .filter(|word| !invalid.contains(*word))
-        .filter(|word| word_fits(word, &rack_counts))
-        .filter_map(|word| {
-            score_word_with_bonuses(word, bonuses, round_multiplier).map(|score| RackCandidate {
-                word: word.clone(),
-                score,
-            })
-        })
-        .collect();
-
-    candidates.sort_by(|a, b| b.score.cmp(&a.score).then_with(|| a.word.cmp(&b.word)));
+    let rack = TileRack::new(tiles);
+    let letter_length = if rack.has_multi_letter_tiles() {
+        None
+    } else {
+        target_length
+    };
+    let dictionary = dictionary::current();
+    let mut candidates: Vec<RackCandidate> = Vec::new();
+    dictionary.for_each_fit(
+        &rack.letter_counts(),
+        rack.blanks(),
+        letter_length,
+        |word| {
+            if invalid.contains(word) {
+                return;
+            }
+            if let Some(breakdown) = best_layout(word, &rack, target_length, scoring) {
+                candidates.push(RackCandidate {
+                    word: word.to_string(),
+                    score: breakdown.total,
+                    from_allowlist: dictionary.is_allowlisted(word),
+                    confidence: ranking.map(|ranking| ranking.log.confidence(&dictionary, word)),
+                    later_cost: hoard.map_or(0.0, |hoard| {
+                        hoard.cost(
+                            breakdown
+                                .tiles
+                                .iter()
+                                .map(|tile| (tile.tile.as_str(), tile.blank)),
+                        )
+                    }),
+                    breakdown,
+                });
+            }
+        },
+    );
+
+    let min_confidence = ranking.and_then(|ranking| ranking.min_confidence);
+    let is_demoted = |candidate: &RackCandidate| {
+        matches!(
+            (candidate.confidence, min_confidence),
+            (Some(confidence), Some(min)) if confidence < min
+        )
+    };
+    candidates.sort_by(|a, b| {
+        is_demoted(a)
+            .cmp(&is_demoted(b))
+            .then_with(|| a.net_score().total_cmp(&b.net_score()).reverse())
+            .then_with(|| b.score.cmp(&a.score))
+            .then_with(|| a.word.cmp(&b.word))
+    });
 
     let max_candidates = limit.max(1);
     if candidates.len() > max_candidates {
@@ -175,13 +124,53 @@ pub fn solve_rack(
     candidates
 }
 
+/// Score of the best word `tiles` can play, without building the candidate
+/// list. Used where many hypothetical racks are scored.
+pub fn best_score(
+    tiles: &[String],
+    target_length: Option<usize>,
+    invalid: &HashSet<String>,
+    scoring: &ScoreContext,
+) -> Option<u32> {
+    let rack = TileRack::new(tiles);
+    let letter_length = if rack.has_multi_letter_tiles() {
+        None
+    } else {
+        target_length
+    };
+    let mut best: Option<u32> = None;
+    dictionary::current().for_each_fit(
+        &rack.letter_counts(),
+        rack.blanks(),
+        letter_length,
+        |word| {
+            if invalid.contains(word) {
+                return;
+            }
+            if let Some(breakdown) = best_layout(word, &rack, target_length, scoring) {
+                best = best.max(Some(breakdown.total));
+            }
+        },
+    );
+    best
+}
+
 pub fn suggest_rerolls(
-    letters: &[char],
+    tiles: &[String],
     target_length: usize,
     _invalid: &HashSet<String>,
     limit: usize,
     baseline_word: Option<&str>,
+    profile: &ScoringProfile,
+    bag: &[u8; 26],
 ) -> Vec<RerollAdvice> {
+    // Blanks and multi-letter tiles are never worth rerolling, so plan around
+    // the single letters and report the rest as held.
+    let (letters, held_tiles): (Vec<&String>, Vec<&String>) = tiles
+        .iter()
+        .partition(|tile| tile.len() == 1 && tile.chars().all(|ch| ch.is_ascii_uppercase()));
+    let letters: Vec<char> = letters.iter().flat_map(|tile| tile.chars()).collect();
+    let letters = letters.as_slice();
     if letters.is_empty() {
         return Vec::new();
     }
@@ -195,7 +184,7 @@ pub fn suggest_rerolls(
     let baseline_counts = baseline_word.map(|word| letter_counts_str(word));
 
     let pass_one = analyze_pass_one(letters, effective_target, baseline_counts.as_ref());
-    let mut advice = vec![pass_one.to_advice()];
+    let mut advice = vec![pass_one.to_advice(bag)];
 
     if advice.len() >= limit {
         advice.truncate(limit);
@@ -207,6 +196,8 @@ pub fn suggest_rerolls(
         effective_target,
         &pass_one,
         baseline_counts.as_ref(),
+        profile,
+        bag,
     ) {
         advice.push(pass_two);
     }
@@ -215,6 +206,9 @@ pub fn suggest_rerolls(
         advice.truncate(limit);
     }
 
+    for entry in &mut advice {
+        entry.held_tiles = held_tiles.iter().map(|tile| tile.to_string()).collect();
+    }
     advice
 }
 
@@ -229,8 +223,9 @@ struct PassOneOutcome {
 }
 
 impl PassOneOutcome {
-    fn to_advice(&self) -> RerollAdvice {
+    fn to_advice(&self, bag: &[u8; 26]) -> RerollAdvice {
         let probability = approximate_draw_probability(
+            bag,
             &self.keep_letters,
             &self.reroll_letters,
             &self.desired_letters,
@@ -240,6 +235,7 @@ impl PassOneOutcome {
             missing_letters: self.desired_letters.clone(),
             reroll_letters: self.reroll_letters.clone(),
             keep_letters: self.keep_letters.clone(),
+            held_tiles: Vec::new(),
             estimated_score: None,
             success_probability: probability,
             phase: "foundation",
@@ -250,7 +246,8 @@ impl PassOneOutcome {
 }
 
 const GLUE_CONSONANTS: &[char] = &['R', 'S', 'T', 'L', 'N', 'D', 'M', 'P', 'C', 'H'];
-const TL_HITTERS: &[char] = &['J', 'X', 'Z', 'K', 'H', 'F', 'W', 'Y', 'V', 'M', 'P', 'C'];
+/// Consonants worth at least this much under the active profile are worth parking on a TL.
+const TL_HITTER_MIN_VALUE: u8 = 3;
 const LENGTHENER_LETTERS: &[char] = &['E', 'R', 'I', 'N', 'G', 'L', 'Y', 'D', 'S'];
 const LENGTHENER_TRIADS: &[&[char]] = &[&['I', 'N', 'G'], &['E', 'R', 'S']];
 const PROTECTED_PAIRS: &[(char, char)] = &[
@@ -549,7 +546,11 @@ fn analyze_pass_two(
     target_length: usize,
     pass_one: &PassOneOutcome,
     baseline_counts: Option<&[u8; 26]>,
+    profile: &ScoringProfile,
+    bag: &[u8; 26],
 ) -> Option<RerollAdvice> {
+    let tl_hitters = tl_hitters(profile);
+    let is_tl_candidate = |ch: char| tl_hitters.contains(&ch);
     let mut keep_flags = pass_one.keep_flags.clone();
     let mut notes: Vec<String> = Vec::new();
     let mut focus_tags: Vec<String> = Vec::new();
@@ -638,12 +639,13 @@ fn analyze_pass_two(
     }
 
     if current_tl_hitters == 0 {
-        for &ch in TL_HITTERS {
+        for &ch in &tl_hitters {
             push_unique_char(&mut desired_letters, ch);
         }
+        let shortlist: Vec<String> = tl_hitters.iter().take(8).map(char::to_string).collect();
         push_note(
             &mut notes,
-            "Fish for a TL hitter (J/X/Z/K/H/F/W/Y)".to_string(),
+            format!("Fish for a TL hitter ({})", shortlist.join("/")),
         );
         push_focus_tag(&mut focus_tags, "Find TL hitter");
     }
@@ -753,13 +755,14 @@ fn analyze_pass_two(
     let desired_snapshot = desired_letters.clone();
     let missing_letters: Vec<char> = desired_snapshot.iter().cloned().take(10).collect();
     let probability =
-        approximate_draw_probability(&keep_letters, &reroll_letters, &desired_snapshot);
+        approximate_draw_probability(bag, &keep_letters, &reroll_letters, &desired_snapshot);
 
     Some(RerollAdvice {
         target_word: "Pass 2 – Target the board".to_string(),
         missing_letters,
         reroll_letters,
         keep_letters,
+        held_tiles: Vec::new(),
         estimated_score: None,
         success_probability: probability,
         phase: "target",
@@ -825,8 +828,16 @@ fn is_lengthener_letter(ch: char) -> bool {
     LENGTHENER_LETTERS.contains(&ch)
 }
 
-fn is_tl_candidate(ch: char) -> bool {
-    TL_HITTERS.contains(&ch)
+/// Consonants (Q aside, which pass one handles) that score well on a TL under
+/// `profile`, highest value first.
+fn tl_hitters(profile: &ScoringProfile) -> Vec<char> {
+    let mut hitters: Vec<(u8, char)> = ('A'..='Z')
+        .filter(|&ch| !is_vowel(ch) && ch != 'Q')
+        .filter_map(|ch| profile.letter_value(ch).map(|value| (value, ch)))
+        .filter(|&(value, _)| value >= TL_HITTER_MIN_VALUE)
+        .collect();
+    hitters.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.1.cmp(&b.1)));
+    hitters.into_iter().map(|(_, ch)| ch).collect()
 }
 
 fn would_break_protected_pair(ch: char, keep_counts: &[u8; 26]) -> bool {
@@ -919,7 +930,10 @@ fn consonant_drop_priority(ch: char) -> i32 {
     }
 }
 
+/// Chance of drawing at least one desired letter, using `bag` as the tile
+/// counts left to draw from.
 fn approximate_draw_probability(
+    bag: &[u8; 26],
     keep_letters: &[char],
     reroll_letters: &[char],
     desired_letters: &[char],
@@ -928,7 +942,7 @@ fn approximate_draw_probability(
         return None;
     }
 
-    let mut bag_counts: [i32; 26] = LETTER_BAG_COUNTS.map(|count| i32::from(count));
+    let mut bag_counts: [i32; 26] = bag.map(|count| i32::from(count));
 
     for &ch in keep_letters {
         if ch.is_ascii_uppercase() {
@@ -998,7 +1012,7 @@ fn approximate_draw_probability(
     Some(success.clamp(0.0, 1.0))
 }
 
-fn binomial(n: u32, k: u32) -> f64 {
+pub fn binomial(n: u32, k: u32) -> f64 {
     if k > n {
         return 0.0;
     }
@@ -1047,58 +1061,203 @@ fn would_violate_baseline(
     current <= required
 }
 
-fn score_word_with_bonuses(word: &str, bonuses: &[Bonus], round_multiplier: u32) -> Option<u32> {
-    let mut sum: u32 = 0;
-    let mut word_multiplier: u32 = 1;
-
-    for (idx, ch) in word.chars().enumerate() {
-        let base = u32::from(scoring::letter_value(ch)?);
-        let bonus = bonuses.get(idx).copied().unwrap_or(Bonus::None);
-        sum += base * bonus.letter_multiplier();
-        word_multiplier = word_multiplier.saturating_mul(bonus.word_multiplier());
-    }
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TileScore {
+    pub tile: String,
+    pub base: u32,
+    pub bonus: Bonus,
+    pub letter_multiplier: u32,
+    pub value: u32,
+    /// Played with a blank tile, which scores nothing.
+    pub blank: bool,
+}
 
-    Some(
-        sum.saturating_mul(word_multiplier)
-            .saturating_mul(round_multiplier),
-    )
+/// The arithmetic behind a word's score, tile by tile, so it can be
+/// checked against the numbers the game shows.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScoreBreakdown {
+    /// Slot square the first tile sits on.
+    pub offset: usize,
+    pub tiles: Vec<TileScore>,
+    pub letter_sum: u32,
+    pub word_multiplier: u32,
+    pub round_multiplier: u32,
+    pub total: u32,
 }
 
-fn word_fits(word: &str, rack_counts: &[u8; 26]) -> bool {
-    let mut need = [0u8; 26];
-    for ch in word.chars() {
-        if !ch.is_ascii_alphabetic() {
-            return false;
+impl ScoreBreakdown {
+    /// One-line summary such as `J(8)×TL + A(1) + M(3) = 28 ×3 round = 84`.
+    pub fn summary(&self) -> String {
+        let tiles: Vec<String> = self
+            .tiles
+            .iter()
+            .map(|tile| match tile.bonus {
+                _ if tile.blank => format!("{}?(0)", tile.tile),
+                Bonus::DoubleLetter | Bonus::TripleLetter => {
+                    format!("{}({})×{}", tile.tile, tile.base, tile.bonus.as_code())
+                }
+                _ => format!("{}({})", tile.tile, tile.base),
+            })
+            .collect();
+        let mut text = format!("{} = {}", tiles.join(" + "), self.letter_sum);
+        if self.offset > 0 {
+            text = format!("From square {}: {text}", self.offset + 1);
+        }
+        if self.word_multiplier > 1 {
+            text.push_str(&format!(" ×{} word", self.word_multiplier));
         }
-        let idx = (ch as u8 - b'A') as usize;
-        need[idx] += 1;
-        if need[idx] > rack_counts[idx] {
-            return false;
+        if self.round_multiplier > 1 {
+            text.push_str(&format!(" ×{} round", self.round_multiplier));
         }
+        if self.word_multiplier > 1 || self.round_multiplier > 1 {
+            text.push_str(&format!(" = {}", self.total));
+        }
+        text
     }
-    true
 }
 
-fn letter_counts(letters: &[char]) -> [u8; 26] {
-    let mut counts = [0u8; 26];
-    for ch in letters {
-        if ch.is_ascii_alphabetic() {
-            let idx = (*ch as u8 - b'A') as usize;
-            counts[idx] += 1;
+/// Scores tiles laid from the first bonus square on. Bonuses apply per tile,
+/// so a `QU` tile on a TL triples both letters.
+pub fn score_tiles(tiles: &[PlacedTile], scoring: &ScoreContext) -> Option<ScoreBreakdown> {
+    score_tiles_at(tiles, scoring, 0)
+}
+
+/// Scores tiles laid from square `offset` of the slot.
+pub fn score_tiles_at(
+    tiles: &[PlacedTile],
+    scoring: &ScoreContext,
+    offset: usize,
+) -> Option<ScoreBreakdown> {
+    let profile = scoring.profile;
+    let mut scores: Vec<TileScore> = Vec::with_capacity(tiles.len());
+    let mut letter_sum: u32 = 0;
+    let mut word_multiplier: u32 = 1;
+
+    for (idx, tile) in tiles.iter().enumerate() {
+        let mut tile_value: u32 = 0;
+        for ch in tile.text.chars() {
+            tile_value += u32::from(profile.letter_value(ch)?);
         }
+        let base = if tile.blank { 0 } else { tile_value };
+        let bonus = scoring
+            .bonuses
+            .get(offset + idx)
+            .copied()
+            .unwrap_or(Bonus::None);
+        let letter_multiplier = profile.letter_multiplier(bonus);
+        let value = base * letter_multiplier;
+        letter_sum += value;
+        word_multiplier = word_multiplier.saturating_mul(profile.word_multiplier(bonus));
+        scores.push(TileScore {
+            tile: tile.text.clone(),
+            base,
+            bonus,
+            letter_multiplier,
+            value,
+            blank: tile.blank,
+        });
     }
-    counts
+    if scores.is_empty() {
+        return None;
+    }
+
+    let round_multiplier = scoring.round_multiplier();
+    Some(ScoreBreakdown {
+        offset,
+        tiles: scores,
+        letter_sum,
+        word_multiplier,
+        round_multiplier,
+        total: letter_sum
+            .saturating_mul(word_multiplier)
+            .saturating_mul(round_multiplier),
+    })
+}
+
+pub fn score_word_with_bonuses(word: &str, scoring: &ScoreContext) -> Option<u32> {
+    score_tiles(&tiles::letter_tiles(word), scoring).map(|breakdown| breakdown.total)
+}
+
+/// Scores `word` as played from the rack `tiles`, picking the highest-scoring
+/// way to lay it out (which multi-letter tiles to use, where blanks go, and
+/// which square of the slot to start on).
+pub fn score_on_rack(
+    word: &str,
+    tiles: &[String],
+    scoring: &ScoreContext,
+) -> Option<ScoreBreakdown> {
+    score_on_rack_at(word, tiles, scoring, None)
+}
+
+/// Like [`score_on_rack`], but with the word starting on square `offset` of
+/// the slot when one is given.
+pub fn score_on_rack_at(
+    word: &str,
+    tiles: &[String],
+    scoring: &ScoreContext,
+    offset: Option<usize>,
+) -> Option<ScoreBreakdown> {
+    best_layout_at(word, &TileRack::new(tiles), None, scoring, offset)
+}
+
+fn best_layout(
+    word: &str,
+    rack: &TileRack,
+    tile_count: Option<usize>,
+    scoring: &ScoreContext,
+) -> Option<ScoreBreakdown> {
+    best_layout_at(word, rack, tile_count, scoring, None)
+}
+
+fn best_layout_at(
+    word: &str,
+    rack: &TileRack,
+    tile_count: Option<usize>,
+    scoring: &ScoreContext,
+    start: Option<usize>,
+) -> Option<ScoreBreakdown> {
+    let mut best: Option<ScoreBreakdown> = None;
+    rack.for_each_layout(word, |layout| {
+        if tile_count.is_some_and(|count| layout.len() != count) {
+            return;
+        }
+        let offsets = scoring.offsets(layout.len());
+        for offset in offsets.filter(|offset| start.is_none_or(|start| start == *offset)) {
+            if let Some(breakdown) = score_tiles_at(layout, scoring, offset) {
+                if best
+                    .as_ref()
+                    .is_none_or(|best| breakdown.total > best.total)
+                {
+                    best = Some(breakdown);
+                }
+            }
+        }
+    });
+    best
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::scoring;
     use std::collections::HashSet;
 
+    fn tiles(text: &str) -> Vec<String> {
+        text.chars().map(|ch| ch.to_string()).collect()
+    }
+
     #[test]
     fn pass_two_chases_lengthener_triads_for_long_targets() {
-        let rack: Vec<char> = "ABCDINT".chars().collect();
-        let advice = suggest_rerolls(&rack, 7, &HashSet::new(), 3, None);
+        let rack = tiles("ABCDINT");
+        let advice = suggest_rerolls(
+            &rack,
+            7,
+            &HashSet::new(),
+            3,
+            None,
+            &scoring::SCRABBLE,
+            &LETTER_BAG_COUNTS,
+        );
         let pass_two = advice
             .iter()
             .find(|entry| entry.phase == "target")
@@ -1111,4 +1270,231 @@ mod tests {
             .iter()
             .any(|note| note.contains("Rack already balanced")));
     }
+
+    #[test]
+    fn low_confidence_words_rank_after_safer_plays() {
+        let dir = std::env::temp_dir().join(format!("yatzy-confidence-{}", std::process::id()));
+        let mut log = AcceptanceLog::open(dir.join("word-outcomes.json")).unwrap();
+        for _ in 0..3 {
+            log.record("ACT", false).unwrap();
+        }
+        std::fs::remove_dir_all(&dir).unwrap();
+        let scoring = ScoreContext {
+            profile: &scoring::SCRABBLE,
+            bonuses: &[],
+            round: 1,
+            slot_length: None,
+        };
+        let solve = |min_confidence| {
+            let ranking = ConfidenceRanking {
+                log: &log,
+                min_confidence,
+            };
+            solve_rack(
+                &tiles("CAT"),
+                None,
+                &HashSet::new(),
+                10,
+                &scoring,
+                Some(&ranking),
+                None,
+            )
+        };
+
+        // ACT and CAT tie on 5 and break the tie alphabetically.
+        let unfiltered = solve(None);
+        assert_eq!(unfiltered[0].word, "ACT");
+        assert!(unfiltered[0].confidence.unwrap() < 0.6);
+
+        // Three rejections put ACT below every safer word, even two-letter ones.
+        let demoted = solve(Some(0.6));
+        assert_eq!(demoted[0].word, "CAT");
+        assert_eq!(demoted.last().unwrap().word, "ACT");
+        assert_eq!(demoted.len(), unfiltered.len());
+    }
+
+    #[test]
+    fn breakdown_matches_rules_worked_example() {
+        // YatzyRules.md: JAM in round 3 with J on a TL scores 84.
+        let bonuses = [Bonus::TripleLetter];
+        let breakdown = score_tiles(
+            &tiles::letter_tiles("JAM"),
+            &ScoreContext {
+                profile: &scoring::SCRABBLE,
+                bonuses: &bonuses,
+                round: 3,
+                slot_length: None,
+            },
+        )
+        .unwrap();
+        assert_eq!(breakdown.tiles[0].value, 24);
+        assert_eq!(breakdown.tiles[2].bonus, Bonus::None);
+        assert_eq!(breakdown.letter_sum, 28);
+        assert_eq!(breakdown.total, 84);
+        assert_eq!(
+            breakdown.summary(),
+            "J(8)×TL + A(1) + M(3) = 28 ×3 round = 84"
+        );
+    }
+
+    #[test]
+    fn blanks_fill_shortfalls_on_the_weakest_squares() {
+        let letters = tiles("QAI?");
+        assert!(TileRack::new(&letters).can_spell("QAT"));
+        assert!(!TileRack::new(&letters).can_spell("QUAT"));
+
+        let bonuses = [Bonus::None, Bonus::None, Bonus::TripleLetter, Bonus::None];
+        let scoring = ScoreContext {
+            profile: &scoring::SCRABBLE,
+            bonuses: &bonuses,
+            round: 1,
+            slot_length: None,
+        };
+        let breakdown = score_on_rack("QAT", &letters, &scoring).unwrap();
+        assert!(breakdown.tiles[2].blank);
+        assert_eq!(breakdown.total, 11);
+
+        let eee = score_on_rack("EEE", &tiles("EE?"), &scoring).unwrap();
+        assert!(!eee.tiles[2].blank);
+        assert_eq!(eee.total, 4);
+
+        let rack = tiles("EAT?");
+        let candidates = solve_rack(&rack, Some(4), &HashSet::new(), 40, &scoring, None, None);
+        let seat = candidates
+            .iter()
+            .find(|candidate| candidate.word == "SEAT")
+            .expect("blank should stand in for the S");
+        assert!(seat.breakdown.tiles[0].blank);
+    }
+
+    #[test]
+    fn multi_letter_tiles_fill_one_square() {
+        let rack: Vec<String> = ["QU", "I", "T", "E", "S"].map(String::from).to_vec();
+        let bonuses = [Bonus::TripleLetter];
+        let scoring = ScoreContext {
+            profile: &scoring::SCRABBLE,
+            bonuses: &bonuses,
+            round: 1,
+            slot_length: None,
+        };
+        let quit = score_on_rack("QUIT", &rack, &scoring).unwrap();
+        assert_eq!(quit.tiles[0].tile, "QU");
+        assert_eq!(quit.total, 35);
+        assert!(!TileRack::new(&rack).can_spell("QI"));
+
+        let candidates = solve_rack(&rack, Some(4), &HashSet::new(), 40, &scoring, None, None);
+        assert!(candidates.iter().any(|candidate| candidate.word == "QUITE"));
+        assert!(candidates
+            .iter()
+            .all(|candidate| candidate.breakdown.tiles.len() == 4));
+    }
+
+    #[test]
+    fn short_words_slide_onto_the_best_squares() {
+        let bonuses = [
+            Bonus::None,
+            Bonus::None,
+            Bonus::None,
+            Bonus::TripleLetter,
+            Bonus::DoubleWord,
+        ];
+        let scoring = ScoreContext {
+            profile: &scoring::SCRABBLE,
+            bonuses: &bonuses,
+            round: 1,
+            slot_length: Some(5),
+        };
+        assert_eq!(scoring.offsets(3), 0..3);
+        assert!(scoring.offsets(6).is_empty());
+
+        // ZA with Z on the TL and A on the DW beats starting on square 0.
+        let za = score_on_rack("ZA", &tiles("ZAE"), &scoring).unwrap();
+        assert_eq!(za.offset, 3);
+        assert_eq!(za.total, 62);
+
+        let candidates = solve_rack(
+            &tiles("ZAEBRAS"),
+            None,
+            &HashSet::new(),
+            40,
+            &scoring,
+            None,
+            None,
+        );
+        assert!(candidates
+            .iter()
+            .all(|candidate| candidate.breakdown.offset + candidate.breakdown.tiles.len() <= 5));
+    }
+
+    #[test]
+    fn held_power_letters_only_trim_big_plays() {
+        let bonuses = [Bonus::TripleLetter, Bonus::DoubleWord];
+        let scoring = ScoreContext {
+            profile: &scoring::SCRABBLE,
+            bonuses: &bonuses,
+            round: 1,
+            slot_length: None,
+        };
+        let hoard = Hoard::new(&scoring::SCRABBLE, 1, 4, &LETTER_BAG_COUNTS).unwrap();
+        let candidates = solve_rack(
+            &tiles("ZAEBRAS"),
+            None,
+            &HashSet::new(),
+            40,
+            &scoring,
+            None,
+            Some(&hoard),
+        );
+
+        // Holding Z for a later slot is worth far less than Z on a TL now.
+        let rank = |word: &str| {
+            candidates
+                .iter()
+                .position(|candidate| candidate.word == word)
+                .unwrap()
+        };
+        assert_eq!(candidates[0].score, 74);
+        assert!(candidates[0].later_cost > 0.0 && candidates[0].later_cost < 20.0);
+        assert!(rank("ZEBRAS") < rank("BARES"));
+    }
+
+    #[test]
+    fn indexed_solve_matches_linear_scan() {
+        let invalid: HashSet<String> = ["TEA".to_string()].into_iter().collect();
+        let bonuses = [Bonus::TripleLetter, Bonus::None, Bonus::DoubleWord];
+        let scoring = ScoreContext {
+            profile: &scoring::SCRABBLE,
+            bonuses: &bonuses,
+            round: 2,
+            slot_length: None,
+        };
+        for rack in ["AERSTLN", "QUIZEAT", "BOOKSEE"] {
+            let letters = tiles(rack);
+            let rack_counts = letter_counts_str(rack);
+            for target in [None, Some(4)] {
+                let mut expected: Vec<(u32, String)> = dictionary::current()
+                    .words()
+                    .filter(|word| target.is_none_or(|len| word.len() == len))
+                    .filter(|word| !invalid.contains(*word))
+                    .filter(|word| {
+                        let need = letter_counts_str(word);
+                        (0..26).all(|idx| need[idx] <= rack_counts[idx])
+                    })
+                    .filter_map(|word| {
+                        score_word_with_bonuses(word, &scoring)
+                            .map(|score| (score, word.to_string()))
+                    })
+                    .collect();
+                expected.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.1.cmp(&b.1)));
+                expected.truncate(40);
+
+                let actual: Vec<(u32, String)> =
+                    solve_rack(&letters, target, &invalid, 40, &scoring, None, None)
+                        .into_iter()
+                        .map(|candidate| (candidate.score, candidate.word))
+                        .collect();
+                assert_eq!(actual, expected, "rack {rack} target {target:?}");
+            }
+        }
+    }
 }
@@ -0,0 +1,49 @@
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+
+/// Reads a file from the app data dir, treating a missing file as empty state.
+pub fn read_optional(path: &Path) -> Result<Option<String>, String> {
+    if !path.is_file() {
+        return Ok(None);
+    }
+    fs::read_to_string(path)
+        .map(Some)
+        .map_err(|err| format!("Failed to read {}: {err}", path.display()))
+}
+
+/// Writes through a sibling temp file so a crash never leaves a half-written store.
+pub fn write_atomic(path: &Path, contents: &str) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|err| format!("Failed to create {}: {err}", parent.display()))?;
+    }
+    let staging = path.with_extension("tmp");
+    fs::write(&staging, contents)
+        .and_then(|_| fs::rename(&staging, path))
+        .map_err(|err| format!("Failed to write {}: {err}", path.display()))
+}
+
+/// Appends one line to an append-only log, creating it on first use.
+pub fn append_line(path: &Path, line: &str) -> Result<(), String> {
+    append_lines(path, &[line])
+}
+
+/// Appends several lines to an append-only log with a single write, so a
+/// failed write can't leave only some of them behind.
+pub fn append_lines<S: AsRef<str>>(path: &Path, lines: &[S]) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|err| format!("Failed to create {}: {err}", parent.display()))?;
+    }
+    let text: String = lines
+        .iter()
+        .map(|line| format!("{}\n", line.as_ref()))
+        .collect();
+    fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .and_then(|mut file| file.write_all(text.as_bytes()))
+        .map_err(|err| format!("Failed to write {}: {err}", path.display()))
+}
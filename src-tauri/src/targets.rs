@@ -0,0 +1,283 @@
+use std::collections::HashSet;
+
+use crate::dictionary;
+use crate::scoring::ScoreContext;
+use crate::solver::{self, RerollAdvice};
+use crate::tiles::TileRack;
+
+/// Most letters a target word may still need from the bag.
+pub const MAX_MISSING: usize = 3;
+
+/// The rack and slot target words are chased on.
+pub struct TargetInputs<'a> {
+    pub tiles: &'a [String],
+    /// Squares the word must fill.
+    pub target_length: usize,
+    pub invalid: &'a HashSet<String>,
+    pub scoring: &'a ScoreContext<'a>,
+    pub bag: &'a [u8; 26],
+    /// Rerolls left to chase the missing letters with.
+    pub rerolls: u8,
+}
+
+/// Dictionary words one to `MAX_MISSING` letters away from the rack, ranked by
+/// score times the exact odds of drawing the missing letters when every
+/// unused letter is rerolled, for as many rerolls as are left.
+pub fn suggest_targets(inputs: &TargetInputs, limit: usize) -> Vec<RerollAdvice> {
+    let rack = TileRack::new(inputs.tiles);
+    let (singles, held): (Vec<&String>, Vec<&String>) = inputs
+        .tiles
+        .iter()
+        .partition(|tile| tile.len() == 1 && tile.chars().all(|ch| ch.is_ascii_uppercase()));
+    let mut single_counts = [0u8; 26];
+    for tile in &singles {
+        single_counts[index(tile.as_bytes()[0])] += 1;
+    }
+    let rack_counts = rack.letter_counts();
+    // Only words that fill the slot are looked up: exactly `target_length`
+    // letters, plus up to one extra per extra letter on multi-letter tiles.
+    let extra_letters: usize = held.iter().map(|tile| tile.len() - 1).sum();
+    let counts = RackCounts {
+        singles: &single_counts,
+        all: &rack_counts,
+        blanks: rack.blanks(),
+    };
+
+    let dictionary = dictionary::current();
+    let mut targets: Vec<(f64, RerollAdvice)> = Vec::new();
+    for length in inputs.target_length..=inputs.target_length + extra_letters {
+        dictionary.for_each_fit(
+            &rack_counts,
+            rack.blanks() + MAX_MISSING,
+            Some(length),
+            |word| {
+                if inputs.invalid.contains(word) {
+                    return;
+                }
+                if let Some(target) = plan_target(inputs, word, &counts, &held) {
+                    targets.push(target);
+                }
+            },
+        );
+    }
+
+    targets.sort_by(|a, b| {
+        b.0.total_cmp(&a.0)
+            .then_with(|| a.1.target_word.cmp(&b.1.target_word))
+    });
+    targets
+        .into_iter()
+        .take(limit)
+        .map(|(_, advice)| advice)
+        .collect()
+}
+
+/// Letter counts of the current rack.
+struct RackCounts<'a> {
+    /// Single-letter tiles, the only ones worth rerolling.
+    singles: &'a [u8; 26],
+    /// Letters across every non-blank tile.
+    all: &'a [u8; 26],
+    blanks: usize,
+}
+
+/// Keep set, rerolls, odds and score for chasing `word`, or `None` when the
+/// rack already plays it or can't reach it with one reroll.
+fn plan_target(
+    inputs: &TargetInputs,
+    word: &str,
+    rack: &RackCounts,
+    held: &[&String],
+) -> Option<(f64, RerollAdvice)> {
+    let mut word_counts = [0u8; 26];
+    for byte in word.bytes() {
+        word_counts[index(byte)] += 1;
+    }
+    let mut missing: [u8; 26] =
+        std::array::from_fn(|idx| word_counts[idx].saturating_sub(rack.all[idx]));
+    // Blanks stand in for the letters the bag is shortest on.
+    for _ in 0..rack.blanks {
+        let Some(scarcest) = (0..26)
+            .filter(|&idx| missing[idx] > 0)
+            .min_by_key(|&idx| inputs.bag[idx])
+        else {
+            break;
+        };
+        missing[scarcest] -= 1;
+    }
+    let needed: u32 = missing.iter().map(|&count| u32::from(count)).sum();
+    if needed == 0 || needed as usize > MAX_MISSING {
+        return None;
+    }
+
+    let keep: [u8; 26] = std::array::from_fn(|idx| word_counts[idx].min(rack.singles[idx]));
+    let reroll: [u8; 26] = std::array::from_fn(|idx| rack.singles[idx] - keep[idx]);
+    let draws: u32 = reroll.iter().map(|&count| u32::from(count)).sum();
+    if draws < needed {
+        return None;
+    }
+
+    let final_tiles: Vec<String> = held
+        .iter()
+        .map(|tile| tile.to_string())
+        .chain(letters(&keep).into_iter().map(String::from))
+        .chain(letters(&missing).into_iter().map(String::from))
+        .collect();
+    let breakdown = solver::score_on_rack(word, &final_tiles, inputs.scoring)
+        .filter(|breakdown| breakdown.tiles.len() == inputs.target_length)?;
+
+    let bag: [u8; 26] = std::array::from_fn(|idx| inputs.bag[idx].saturating_sub(keep[idx]));
+    let probability = completion_probability(&bag, draws, &missing, inputs.rerolls);
+    if probability <= 0.0 {
+        return None;
+    }
+
+    let missing_letters = letters(&missing);
+    let advice = RerollAdvice {
+        target_word: word.to_string(),
+        notes: vec![format!(
+            "Needs {} from {draws} rerolled tile{} over {} reroll{}: {:.1}% for {} points",
+            missing_letters.iter().collect::<String>(),
+            if draws == 1 { "" } else { "s" },
+            inputs.rerolls,
+            if inputs.rerolls == 1 { "" } else { "s" },
+            probability * 100.0,
+            breakdown.total
+        )],
+        missing_letters,
+        reroll_letters: letters(&reroll),
+        keep_letters: letters(&keep),
+        held_tiles: held.iter().map(|tile| tile.to_string()).collect(),
+        estimated_score: Some(breakdown.total),
+        success_probability: Some(probability),
+        phase: "target_word",
+        focus_tags: vec!["target-word".to_string()],
+    };
+    Some((probability * f64::from(breakdown.total), advice))
+}
+
+/// Exact chance of completing `needed` within `tries` rerolls of `draws`
+/// tiles from `bag`, keeping every missing letter that turns up and
+/// rerolling the rest again.
+pub fn completion_probability(bag: &[u8; 26], draws: u32, needed: &[u8; 26], tries: u8) -> f64 {
+    if needed.iter().all(|&count| count == 0) {
+        return 1.0;
+    }
+    let total: u32 = bag.iter().map(|&count| u32::from(count)).sum();
+    let outcomes = solver::binomial(total, draws);
+    if tries == 0 || outcomes == 0.0 {
+        return 0.0;
+    }
+    let letters: Vec<usize> = (0..26).filter(|&idx| needed[idx] > 0).collect();
+    let chase = Chase {
+        bag,
+        draws,
+        needed,
+        tries,
+        others: total - letters.iter().map(|&idx| u32::from(bag[idx])).sum::<u32>(),
+    };
+    chase.ways(&letters, draws, 1.0, &mut [0u8; 26]) / outcomes
+}
+
+/// One reroll toward the missing letters of a target word.
+struct Chase<'a> {
+    bag: &'a [u8; 26],
+    draws: u32,
+    needed: &'a [u8; 26],
+    tries: u8,
+    /// Tiles in the bag that are no use to the word.
+    others: u32,
+}
+
+impl Chase<'_> {
+    /// Ways to draw the tiles still `remaining` once the count of each letter
+    /// before `letters` is fixed, each weighted by the chance of finishing the
+    /// word from there.
+    fn ways(&self, letters: &[usize], remaining: u32, ways: f64, hits: &mut [u8; 26]) -> f64 {
+        let Some((&idx, rest)) = letters.split_first() else {
+            let ways = ways * solver::binomial(self.others, remaining);
+            let left: [u8; 26] = std::array::from_fn(|idx| self.needed[idx] - hits[idx]);
+            if ways == 0.0 || left.iter().all(|&count| count == 0) {
+                return ways;
+            }
+            let bag: [u8; 26] = std::array::from_fn(|idx| self.bag[idx] - hits[idx]);
+            let kept: u32 = hits.iter().map(|&count| u32::from(count)).sum();
+            return ways * completion_probability(&bag, self.draws - kept, &left, self.tries - 1);
+        };
+        let total = (0..=remaining.min(u32::from(self.bag[idx])))
+            .map(|take| {
+                hits[idx] = self.needed[idx].min(take as u8);
+                let ways = ways * solver::binomial(u32::from(self.bag[idx]), take);
+                self.ways(rest, remaining - take, ways, hits)
+            })
+            .sum();
+        hits[idx] = 0;
+        total
+    }
+}
+
+fn letters(counts: &[u8; 26]) -> Vec<char> {
+    counts
+        .iter()
+        .enumerate()
+        .flat_map(|(idx, &count)| std::iter::repeat_n((b'A' + idx as u8) as char, count.into()))
+        .collect()
+}
+
+fn index(byte: u8) -> usize {
+    (byte - b'A') as usize
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scoring::{Bonus, SCRABBLE};
+    use crate::solver::LETTER_BAG_COUNTS;
+
+    #[test]
+    fn targets_report_exact_odds_and_slot_scores() {
+        // One draw from a 98-tile bag: 12 Es.
+        let mut needed = [0u8; 26];
+        needed[index(b'E')] = 1;
+        let one = completion_probability(&LETTER_BAG_COUNTS, 1, &needed, 1);
+        assert!((one - 12.0 / 98.0).abs() < 1e-12);
+        // Two draws: 1 - P(no E) = 1 - (86/98)(85/97).
+        let two = completion_probability(&LETTER_BAG_COUNTS, 2, &needed, 1);
+        assert!((two - (1.0 - 86.0 * 85.0 / (98.0 * 97.0))).abs() < 1e-12);
+        // One tile, two tries: the missed tile goes back before the retry.
+        let retry = completion_probability(&LETTER_BAG_COUNTS, 1, &needed, 2);
+        assert!((retry - (1.0 - (86.0 / 98.0) * (86.0 / 98.0))).abs() < 1e-12);
+
+        let invalid = HashSet::new();
+        let bonuses = [Bonus::TripleLetter];
+        let scoring = ScoreContext {
+            profile: &SCRABBLE,
+            bonuses: &bonuses,
+            round: 2,
+            slot_length: None,
+        };
+        let tiles: Vec<String> = "JMV".chars().map(String::from).collect();
+        let targets = suggest_targets(
+            &TargetInputs {
+                tiles: &tiles,
+                target_length: 3,
+                invalid: &invalid,
+                scoring: &scoring,
+                bag: &LETTER_BAG_COUNTS,
+                rerolls: 1,
+            },
+            5,
+        );
+        let jam = targets
+            .iter()
+            .find(|target| target.target_word == "JAM")
+            .expect("JAM is one A away");
+        assert_eq!(jam.keep_letters, vec!['J', 'M']);
+        assert_eq!(jam.reroll_letters, vec!['V']);
+        assert_eq!(jam.missing_letters, vec!['A']);
+        // J(8)×TL + A(1) + M(3) = 28, ×2 round.
+        assert_eq!(jam.estimated_score, Some(56));
+        let odds = jam.success_probability.unwrap();
+        assert!((odds - 9.0 / 96.0).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,207 @@
+use crate::scoring::BLANK;
+
+/// Longest multi-letter tile accepted in a rack, e.g. `QU` or `ING`.
+pub const MAX_TILE_LEN: usize = 3;
+/// Most tiles a rack may hold; word search grows with every extra tile.
+pub const MAX_RACK_TILES: usize = 10;
+/// Most blanks a rack may hold, since each one can stand for any letter.
+pub const MAX_BLANKS: usize = 2;
+
+/// A rack tile as laid on one square of a slot.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlacedTile {
+    pub text: String,
+    /// A blank standing in for `text`, which scores nothing.
+    pub blank: bool,
+}
+
+/// Normalizes a rack entry into a tile: `?` for a blank, or 1 to
+/// `MAX_TILE_LEN` letters.
+pub fn parse_tile(entry: &str) -> Option<String> {
+    let tile = entry.trim().to_ascii_uppercase();
+    let is_blank = tile.len() == 1 && tile.starts_with(BLANK);
+    let is_letters =
+        (1..=MAX_TILE_LEN).contains(&tile.len()) && tile.chars().all(|ch| ch.is_ascii_alphabetic());
+    (is_blank || is_letters).then_some(tile)
+}
+
+/// Rejects racks with more tiles or blanks than the solver searches.
+pub fn check_rack_size<S: AsRef<str>>(tiles: &[S]) -> Result<(), String> {
+    if tiles.len() > MAX_RACK_TILES {
+        return Err(format!(
+            "A rack holds at most {MAX_RACK_TILES} tiles, got {}.",
+            tiles.len()
+        ));
+    }
+    let blanks = TileRack::new(tiles).blanks();
+    if blanks > MAX_BLANKS {
+        return Err(format!(
+            "A rack holds at most {MAX_BLANKS} blanks, got {blanks}."
+        ));
+    }
+    Ok(())
+}
+
+/// Lays `word` out one letter per square, with no blanks.
+pub fn letter_tiles(word: &str) -> Vec<PlacedTile> {
+    word.chars()
+        .map(|ch| PlacedTile {
+            text: ch.to_ascii_uppercase().to_string(),
+            blank: false,
+        })
+        .collect()
+}
+
+/// The tiles on a rack, counted by kind.
+#[derive(Debug, Clone, Default)]
+pub struct TileRack {
+    letters: [u8; 26],
+    multi: Vec<(String, u8)>,
+    blanks: usize,
+}
+
+impl TileRack {
+    pub fn new<S: AsRef<str>>(tiles: &[S]) -> Self {
+        let mut rack = Self::default();
+        for tile in tiles {
+            let tile = tile.as_ref();
+            if tile.len() == 1 && tile.starts_with(BLANK) {
+                rack.blanks += 1;
+            } else if tile.len() == 1 {
+                if let Some(idx) = letter_index(tile.chars().next().unwrap_or(BLANK)) {
+                    rack.letters[idx] += 1;
+                }
+            } else if tile.chars().all(|ch| ch.is_ascii_uppercase()) {
+                match rack.multi.iter_mut().find(|(text, _)| text == tile) {
+                    Some((_, count)) => *count += 1,
+                    None => rack.multi.push((tile.to_string(), 1)),
+                }
+            }
+        }
+        rack
+    }
+
+    pub fn blanks(&self) -> usize {
+        self.blanks
+    }
+
+    pub fn has_multi_letter_tiles(&self) -> bool {
+        !self.multi.is_empty()
+    }
+
+    /// Letters across every non-blank tile, for looking words up in the anagram index.
+    pub fn letter_counts(&self) -> [u8; 26] {
+        let mut counts = self.letters;
+        for (text, count) in &self.multi {
+            for ch in text.chars() {
+                if let Some(idx) = letter_index(ch) {
+                    counts[idx] += count;
+                }
+            }
+        }
+        counts
+    }
+
+    /// Calls `visit` with every way to spell `word` from this rack's tiles.
+    /// Multi-letter tiles are tried first, then single letters, then blanks.
+    pub fn for_each_layout<F>(&self, word: &str, mut visit: F)
+    where
+        F: FnMut(&[PlacedTile]),
+    {
+        let mut remaining = self.clone();
+        let mut layout: Vec<PlacedTile> = Vec::new();
+        remaining.walk(word, &mut layout, &mut visit);
+    }
+
+    #[cfg(test)]
+    pub fn can_spell(&self, word: &str) -> bool {
+        let mut found = false;
+        self.for_each_layout(word, |_| found = true);
+        found
+    }
+
+    fn walk<F>(&mut self, rest: &str, layout: &mut Vec<PlacedTile>, visit: &mut F)
+    where
+        F: FnMut(&[PlacedTile]),
+    {
+        let Some(first) = rest.chars().next() else {
+            visit(layout);
+            return;
+        };
+
+        for multi_idx in 0..self.multi.len() {
+            let (text, count) = &self.multi[multi_idx];
+            if *count == 0 || !rest.starts_with(text.as_str()) {
+                continue;
+            }
+            let text = text.clone();
+            self.multi[multi_idx].1 -= 1;
+            layout.push(PlacedTile {
+                text: text.clone(),
+                blank: false,
+            });
+            self.walk(&rest[text.len()..], layout, visit);
+            layout.pop();
+            self.multi[multi_idx].1 += 1;
+        }
+
+        let Some(idx) = letter_index(first) else {
+            return;
+        };
+        let tail = &rest[first.len_utf8()..];
+        for blank in [false, true] {
+            let available = if blank {
+                self.blanks > 0
+            } else {
+                self.letters[idx] > 0
+            };
+            if !available {
+                continue;
+            }
+            self.take(idx, blank, -1);
+            layout.push(PlacedTile {
+                text: first.to_string(),
+                blank,
+            });
+            self.walk(tail, layout, visit);
+            layout.pop();
+            self.take(idx, blank, 1);
+        }
+    }
+
+    fn take(&mut self, idx: usize, blank: bool, delta: i8) {
+        if blank {
+            self.blanks = self.blanks.saturating_add_signed(isize::from(delta));
+        } else {
+            self.letters[idx] = self.letters[idx].saturating_add_signed(delta);
+        }
+    }
+}
+
+fn letter_index(ch: char) -> Option<usize> {
+    ch.is_ascii_uppercase().then(|| (ch as u8 - b'A') as usize)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn multi_letter_tiles_are_used_whole() {
+        assert_eq!(parse_tile(" qu "), Some("QU".to_string()));
+        assert_eq!(parse_tile("?"), Some("?".to_string()));
+        assert_eq!(parse_tile("Q-U"), None);
+        assert_eq!(parse_tile("ABCD"), None);
+
+        let rack = TileRack::new(&["QU", "I", "T", "?"]);
+        assert_eq!(rack.letter_counts()[(b'U' - b'A') as usize], 1);
+        assert!(rack.can_spell("QUIT"));
+        assert!(!TileRack::new(&["QU", "I"]).can_spell("QI"));
+
+        let mut layouts: Vec<Vec<String>> = Vec::new();
+        rack.for_each_layout("QUITS", |tiles| {
+            layouts.push(tiles.iter().map(|tile| tile.text.clone()).collect());
+        });
+        assert_eq!(layouts, vec![vec!["QU", "I", "T", "S"]]);
+    }
+}
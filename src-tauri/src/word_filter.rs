@@ -0,0 +1,113 @@
+//! Word-list filters shared by `build.rs` (which compiles the embedded
+//! dictionary) and the solver. Keep this file free of crate dependencies so
+//! the build script can include it with `#[path]`.
+
+use std::collections::HashSet;
+
+pub const MIN_WORD_LEN: usize = 2;
+pub const MAX_WORD_LEN: usize = 15;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rejection {
+    NotInCoreWords,
+    WrongLength,
+    NotAlphabetic,
+    NotInCspell,
+    NoVowel,
+    UniformCharacters,
+    Blocklisted,
+}
+
+pub trait WordSet {
+    fn contains_word(&self, word: &str) -> bool;
+}
+
+impl WordSet for HashSet<String> {
+    fn contains_word(&self, word: &str) -> bool {
+        self.contains(word)
+    }
+}
+
+/// Trims, drops blanks and `#` comments, and upper-cases every line of a word list.
+pub fn parse_word_list(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.lines()
+        .map(|line| line.trim())
+        .filter(|line| !line.is_empty())
+        .filter(|line| !line.starts_with('#'))
+        .map(|line| line.to_ascii_uppercase())
+}
+
+/// Dictionary filters in the order the pipeline applies them.
+const PIPELINE: [Rejection; 7] = [
+    Rejection::NotInCoreWords,
+    Rejection::WrongLength,
+    Rejection::NotAlphabetic,
+    Rejection::NotInCspell,
+    Rejection::NoVowel,
+    Rejection::UniformCharacters,
+    Rejection::Blocklisted,
+];
+
+/// Runs an upper-cased wordlist entry through the dictionary filters in
+/// order and returns the first one it fails, without running the rest.
+pub fn first_rejection(
+    word: &str,
+    core_words: &impl WordSet,
+    cspell_words: &impl WordSet,
+    blocklist: &impl WordSet,
+) -> Option<Rejection> {
+    PIPELINE
+        .into_iter()
+        .find(|&filter| fails(filter, word, core_words, cspell_words, blocklist))
+}
+
+/// Every dictionary filter an upper-cased wordlist entry fails, in pipeline
+/// order. Only for explaining a single word; use `first_rejection` to filter.
+pub fn rejections(
+    word: &str,
+    core_words: &impl WordSet,
+    cspell_words: &impl WordSet,
+    blocklist: &impl WordSet,
+) -> Vec<Rejection> {
+    PIPELINE
+        .into_iter()
+        .filter(|&filter| fails(filter, word, core_words, cspell_words, blocklist))
+        .collect()
+}
+
+fn fails(
+    filter: Rejection,
+    word: &str,
+    core_words: &impl WordSet,
+    cspell_words: &impl WordSet,
+    blocklist: &impl WordSet,
+) -> bool {
+    match filter {
+        Rejection::NotInCoreWords => !core_words.contains_word(word),
+        Rejection::WrongLength => !(MIN_WORD_LEN..=MAX_WORD_LEN).contains(&word.len()),
+        Rejection::NotAlphabetic => !word.chars().all(|c| c.is_ascii_alphabetic()),
+        Rejection::NotInCspell => !cspell_words.contains_word(word),
+        Rejection::NoVowel => !contains_vowel(word),
+        Rejection::UniformCharacters => is_uniform_character(word),
+        Rejection::Blocklisted => blocklist.contains_word(word),
+    }
+}
+
+pub fn has_valid_shape(word: &str) -> bool {
+    (MIN_WORD_LEN..=MAX_WORD_LEN).contains(&word.len())
+        && word.chars().all(|c| c.is_ascii_alphabetic())
+}
+
+pub fn contains_vowel(word: &str) -> bool {
+    word.chars()
+        .any(|ch| matches!(ch, 'A' | 'E' | 'I' | 'O' | 'U' | 'Y'))
+}
+
+pub fn is_uniform_character(word: &str) -> bool {
+    let mut chars = word.chars();
+    if let Some(first) = chars.next() {
+        chars.all(|ch| ch == first)
+    } else {
+        false
+    }
+}
@@ -0,0 +1,144 @@
+use std::collections::BTreeSet;
+use std::path::PathBuf;
+
+use crate::storage;
+use crate::word_filter;
+
+/// A user-maintained set of words saved as a plain text file, one word per
+/// line, in the same format as the bundled `invalid-words.txt`.
+pub struct WordStore {
+    path: PathBuf,
+    header: &'static str,
+    words: BTreeSet<String>,
+}
+
+impl WordStore {
+    pub fn open(path: PathBuf, header: &'static str) -> Result<Self, String> {
+        let words = storage::read_optional(&path)?
+            .map(|text| parse_words(&text))
+            .unwrap_or_default();
+        Ok(Self {
+            path,
+            header,
+            words,
+        })
+    }
+
+    pub fn words(&self) -> &BTreeSet<String> {
+        &self.words
+    }
+
+    pub fn list(&self) -> Vec<String> {
+        self.words.iter().cloned().collect()
+    }
+
+    /// Adds the given words and returns how many were new.
+    pub fn add<I, S>(&mut self, words: I) -> Result<usize, String>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut updated = self.words.clone();
+        let mut added = 0;
+        for word in words {
+            if let Some(word) = normalize_word(word.as_ref()) {
+                if updated.insert(word) {
+                    added += 1;
+                }
+            }
+        }
+        if added > 0 {
+            self.save(updated)?;
+        }
+        Ok(added)
+    }
+
+    /// Removes the given words and returns how many were present.
+    pub fn remove<I, S>(&mut self, words: I) -> Result<usize, String>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut updated = self.words.clone();
+        let mut removed = 0;
+        for word in words {
+            if let Some(word) = normalize_word(word.as_ref()) {
+                if updated.remove(&word) {
+                    removed += 1;
+                }
+            }
+        }
+        if removed > 0 {
+            self.save(updated)?;
+        }
+        Ok(removed)
+    }
+
+    /// Merges a shared word list (one word per line, `#` comments allowed).
+    pub fn import(&mut self, text: &str) -> Result<usize, String> {
+        self.add(parse_words(text))
+    }
+
+    pub fn export(&self) -> String {
+        render(self.header, &self.words)
+    }
+
+    /// Writes `words` to disk and only then makes them the current set, so a
+    /// failed write leaves the store as it was.
+    fn save(&mut self, words: BTreeSet<String>) -> Result<(), String> {
+        storage::write_atomic(&self.path, &render(self.header, &words))?;
+        self.words = words;
+        Ok(())
+    }
+}
+
+fn render(header: &str, words: &BTreeSet<String>) -> String {
+    let mut text = String::new();
+    for line in header.lines() {
+        text.push_str("# ");
+        text.push_str(line);
+        text.push('\n');
+    }
+    for word in words {
+        text.push_str(word);
+        text.push('\n');
+    }
+    text
+}
+
+fn parse_words(text: &str) -> BTreeSet<String> {
+    word_filter::parse_word_list(text)
+        .filter_map(|word| normalize_word(&word))
+        .collect()
+}
+
+fn normalize_word(word: &str) -> Option<String> {
+    let word = word.trim().to_ascii_uppercase();
+    if word.is_empty() || !word.chars().all(|ch| ch.is_ascii_alphabetic()) {
+        return None;
+    }
+    Some(word)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn changes_persist_across_reopen() {
+        let dir = std::env::temp_dir().join(format!("yatzy-word-store-{}", std::process::id()));
+        let path = dir.join("blocked-words.txt");
+
+        let mut store = WordStore::open(path.clone(), "Blocked words").unwrap();
+        assert_eq!(store.add(["qat", " Xu ", "no-go", ""]).unwrap(), 2);
+        assert_eq!(store.import("# shared list\nZAX\nqat\n").unwrap(), 1);
+        assert_eq!(store.remove(["XU", "JO"]).unwrap(), 1);
+
+        let reopened = WordStore::open(path, "Blocked words").unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(reopened.list(), vec!["QAT", "ZAX"]);
+        assert_eq!(reopened.export(), "# Blocked words\nQAT\nZAX\n");
+    }
+}